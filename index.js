@@ -5,16 +5,14 @@ import('./pkg')
 
     const forest_button = document.getElementById('forest');
     forest_button.addEventListener('click', async () => {
-      const promise = new rust_module.Forest(ctx, BigInt('42'));
-      const forest = await promise;
+      const forest = await rust_module.Forest.load(ctx, BigInt('42'));
       player.set_song(forest.into_song());
       player.play();
     });
 
     const metronome_button = document.getElementById('metronome');
     metronome_button.addEventListener('click', async () => {
-      const promise = new rust_module.Metronome(ctx, 120);
-      const metronome = await promise;
+      const metronome = await rust_module.Metronome.load(ctx, 120);
       player.set_song(metronome.into_song());
       player.play();
     });
@@ -0,0 +1,416 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+};
+
+use wasm_bindgen::JsValue;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArrangementErrorKind {
+    EmptyPatterns,
+    UnknownSection,
+    ZeroRepeats,
+    MissingMarker,
+    JumpCycle,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArrangementError {
+    kind: ArrangementErrorKind,
+    message: String,
+}
+
+impl ArrangementError {
+    pub fn new<S: Into<String>>(kind: ArrangementErrorKind, message: S) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    #[inline]
+    pub fn kind(&self) -> &ArrangementErrorKind {
+        &self.kind
+    }
+
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for ArrangementError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ArrangementError {}
+
+impl From<ArrangementError> for JsValue {
+    #[inline]
+    fn from(value: ArrangementError) -> Self {
+        JsValue::from_str(&value.to_string())
+    }
+}
+
+// NOTE: Patterns are page indices of the sequencer, played in order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    name: String,
+    patterns: Vec<usize>,
+}
+
+impl Section {
+    pub fn new<S: Into<String>>(name: S, patterns: Vec<usize>) -> Result<Self, ArrangementError> {
+        let name = name.into();
+        if patterns.is_empty() {
+            return Err(ArrangementError::new(
+                ArrangementErrorKind::EmptyPatterns,
+                format!("section {name} has no patterns"),
+            ));
+        }
+        Ok(Self { name, patterns })
+    }
+
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline]
+    pub fn patterns(&self) -> &[usize] {
+        &self.patterns
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Entry {
+    Play {
+        section: String,
+        repeats: usize,
+    },
+    Marker(String),
+    // NOTE: Jumps back (or ahead) to the marker the given times, or forever when None
+    Jump {
+        marker: String,
+        times: Option<usize>,
+    },
+}
+
+impl Entry {
+    #[inline]
+    pub fn play<S: Into<String>>(section: S, repeats: usize) -> Self {
+        Entry::Play {
+            section: section.into(),
+            repeats,
+        }
+    }
+
+    #[inline]
+    pub fn marker<S: Into<String>>(name: S) -> Self {
+        Entry::Marker(name.into())
+    }
+
+    #[inline]
+    pub fn jump<S: Into<String>>(marker: S, times: Option<usize>) -> Self {
+        Entry::Jump {
+            marker: marker.into(),
+            times,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Arrangement {
+    sections: Vec<Section>,
+    order: Vec<Entry>,
+    index: usize,
+    repeat: usize,
+    pattern: usize,
+    jumps: HashMap<usize, usize>,
+}
+
+impl Arrangement {
+    pub fn new(sections: Vec<Section>, order: Vec<Entry>) -> Result<Self, ArrangementError> {
+        let arrangement = Self {
+            sections,
+            order,
+            index: 0,
+            repeat: 0,
+            pattern: 0,
+            jumps: HashMap::new(),
+        };
+
+        for entry in &arrangement.order {
+            match entry {
+                Entry::Play { section, repeats } => {
+                    if arrangement.section(section).is_none() {
+                        return Err(ArrangementError::new(
+                            ArrangementErrorKind::UnknownSection,
+                            format!("unknown section: {section}"),
+                        ));
+                    }
+                    if *repeats == 0 {
+                        return Err(ArrangementError::new(
+                            ArrangementErrorKind::ZeroRepeats,
+                            format!("section {section} is played zero times"),
+                        ));
+                    }
+                }
+                Entry::Marker(_) => {}
+                Entry::Jump { marker, .. } => {
+                    arrangement.find_marker(marker)?;
+                }
+            }
+        }
+        arrangement.walk()?;
+        Ok(arrangement)
+    }
+
+    // NOTE: Walks the order like next_pattern, a state seen again without a play in between is a
+    //       cycle of jumps that would never yield a pattern, and one seen after a play repeats forever
+    fn walk(&self) -> Result<(), ArrangementError> {
+        let mut seen: HashMap<(usize, BTreeMap<usize, usize>), usize> = HashMap::new();
+        let mut jumps: BTreeMap<usize, usize> = BTreeMap::new();
+        let mut index = 0;
+        let mut plays = 0;
+
+        while let Some(entry) = self.order.get(index) {
+            if let Some(seen_plays) = seen.insert((index, jumps.clone()), plays) {
+                if seen_plays < plays {
+                    return Ok(());
+                }
+                return Err(ArrangementError::new(
+                    ArrangementErrorKind::JumpCycle,
+                    "jumps loop without playing a section",
+                ));
+            }
+
+            match entry {
+                Entry::Play { .. } => {
+                    plays += 1;
+                    index += 1;
+                }
+                Entry::Marker(_) => index += 1,
+                Entry::Jump {
+                    marker,
+                    times: None,
+                } => index = self.find_marker(marker)?,
+                Entry::Jump {
+                    marker,
+                    times: Some(times),
+                } => {
+                    let taken = jumps.entry(index).or_default();
+                    if *taken < *times {
+                        *taken += 1;
+                        index = self.find_marker(marker)?;
+                    } else {
+                        jumps.remove(&index);
+                        index += 1;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    pub fn sections(&self) -> &[Section] {
+        &self.sections
+    }
+
+    #[inline]
+    pub fn order(&self) -> &[Entry] {
+        &self.order
+    }
+
+    fn section(&self, name: &str) -> Option<&Section> {
+        self.sections.iter().find(|section| section.name() == name)
+    }
+
+    fn marker(&self, name: &str) -> Option<usize> {
+        self.order
+            .iter()
+            .position(|entry| matches!(entry, Entry::Marker(marker) if marker == name))
+    }
+
+    fn find_marker(&self, name: &str) -> Result<usize, ArrangementError> {
+        self.marker(name).ok_or_else(|| {
+            ArrangementError::new(
+                ArrangementErrorKind::MissingMarker,
+                format!("missing marker: {name}"),
+            )
+        })
+    }
+
+    // NOTE: The section of the pattern returned last by next_pattern
+    pub fn current_section(&self) -> Option<&str> {
+        match self.order.get(self.index)? {
+            Entry::Play { section, .. } => Some(section),
+            _ => None,
+        }
+    }
+
+    pub fn restart(&mut self) {
+        self.index = 0;
+        self.repeat = 0;
+        self.pattern = 0;
+        self.jumps.clear();
+    }
+
+    pub fn next_pattern(&mut self) -> Option<usize> {
+        loop {
+            match self.order.get(self.index)? {
+                Entry::Play { section, repeats } => {
+                    let patterns = self.section(section)?.patterns();
+                    if let Some(pattern) = patterns.get(self.pattern) {
+                        let pattern = *pattern;
+                        self.pattern += 1;
+                        return Some(pattern);
+                    }
+
+                    self.pattern = 0;
+                    self.repeat += 1;
+                    if self.repeat >= *repeats {
+                        self.repeat = 0;
+                        self.index += 1;
+                    }
+                }
+                Entry::Marker(_) => self.index += 1,
+                Entry::Jump { marker, times } => {
+                    let taken = self.jumps.entry(self.index).or_default();
+                    if times.is_none_or(|times| *taken < times) {
+                        *taken += 1;
+                        self.index = self.marker(marker)?;
+                    } else {
+                        // NOTE: Resets the count so an outer loop can take the jump again
+                        self.jumps.remove(&self.index);
+                        self.index += 1;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sections() -> Vec<Section> {
+        vec![
+            Section::new("intro", vec![0]).unwrap(),
+            Section::new("A", vec![1, 2]).unwrap(),
+            Section::new("B", vec![3]).unwrap(),
+            Section::new("outro", vec![4]).unwrap(),
+        ]
+    }
+
+    fn patterns(arrangement: &mut Arrangement, limit: usize) -> Vec<usize> {
+        std::iter::from_fn(|| arrangement.next_pattern())
+            .take(limit)
+            .collect()
+    }
+
+    #[test]
+    fn test_repeats() {
+        let mut arrangement = Arrangement::new(
+            sections(),
+            vec![
+                Entry::play("intro", 1),
+                Entry::play("A", 2),
+                Entry::play("B", 1),
+                Entry::play("outro", 1),
+            ],
+        )
+        .unwrap();
+        assert_eq!(patterns(&mut arrangement, 100), vec![0, 1, 2, 1, 2, 3, 4]);
+        assert_eq!(arrangement.next_pattern(), None);
+
+        arrangement.restart();
+        assert_eq!(arrangement.next_pattern(), Some(0));
+        assert_eq!(arrangement.current_section(), Some("intro"));
+    }
+
+    #[test]
+    fn test_jumps() {
+        let mut arrangement = Arrangement::new(
+            sections(),
+            vec![
+                Entry::play("intro", 1),
+                Entry::marker("verse"),
+                Entry::play("A", 1),
+                Entry::marker("chorus"),
+                Entry::play("B", 1),
+                Entry::jump("chorus", Some(1)),
+                Entry::jump("verse", Some(1)),
+                Entry::play("outro", 1),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            patterns(&mut arrangement, 100),
+            vec![0, 1, 2, 3, 3, 1, 2, 3, 3, 4]
+        );
+    }
+
+    #[test]
+    fn test_loop() {
+        let mut arrangement = Arrangement::new(
+            sections(),
+            vec![
+                Entry::play("intro", 1),
+                Entry::marker("loop"),
+                Entry::play("B", 1),
+                Entry::jump("loop", None),
+            ],
+        )
+        .unwrap();
+        assert_eq!(patterns(&mut arrangement, 5), vec![0, 3, 3, 3, 3]);
+        assert_eq!(arrangement.current_section(), Some("B"));
+    }
+
+    #[test]
+    fn test_new() {
+        let kind = |order: Vec<Entry>| Arrangement::new(sections(), order).unwrap_err().kind;
+
+        assert_eq!(
+            Section::new("empty", vec![]).unwrap_err().kind(),
+            &ArrangementErrorKind::EmptyPatterns
+        );
+        assert_eq!(
+            kind(vec![Entry::play("C", 1)]),
+            ArrangementErrorKind::UnknownSection
+        );
+        assert_eq!(
+            kind(vec![Entry::play("A", 0)]),
+            ArrangementErrorKind::ZeroRepeats
+        );
+        assert_eq!(
+            kind(vec![Entry::jump("nowhere", Some(1))]),
+            ArrangementErrorKind::MissingMarker
+        );
+        assert_eq!(
+            kind(vec![Entry::marker("stuck"), Entry::jump("stuck", None)]),
+            ArrangementErrorKind::JumpCycle
+        );
+        assert_eq!(
+            kind(vec![
+                Entry::marker("x"),
+                Entry::jump("y", None),
+                Entry::play("A", 1),
+                Entry::marker("y"),
+                Entry::jump("x", None),
+            ]),
+            ArrangementErrorKind::JumpCycle
+        );
+        assert_eq!(
+            Arrangement::new(sections(), vec![Entry::play("C", 1)])
+                .unwrap_err()
+                .to_string(),
+            "unknown section: C"
+        );
+        assert!(Arrangement::new(sections(), vec![]).is_ok());
+    }
+}
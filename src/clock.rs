@@ -0,0 +1,175 @@
+use std::{cell::Cell, rc::Rc};
+
+use web_sys::AudioContext;
+
+// NOTE: Times are in seconds on the AudioContext timeline, which the sequencer schedules against
+pub trait Clock {
+    fn current_time(&self) -> f64;
+}
+
+impl Clock for AudioContext {
+    #[inline]
+    fn current_time(&self) -> f64 {
+        (**self).current_time()
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for Rc<C> {
+    #[inline]
+    fn current_time(&self) -> f64 {
+        (**self).current_time()
+    }
+}
+
+// NOTE: Clones share the same time, so a test or an offline renderer can step the clock a song holds
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock {
+    time: Rc<Cell<f64>>,
+}
+
+impl ManualClock {
+    pub fn new(time: f64) -> Self {
+        Self {
+            time: Rc::new(Cell::new(time)),
+        }
+    }
+
+    #[inline]
+    pub fn set(&self, time: f64) {
+        self.time.set(time);
+    }
+
+    #[inline]
+    pub fn advance(&self, seconds: f64) {
+        self.time.set(self.time.get() + seconds);
+    }
+}
+
+impl Clock for ManualClock {
+    #[inline]
+    fn current_time(&self) -> f64 {
+        self.time.get()
+    }
+}
+
+// NOTE: Follows an external timeline, e.g. a host or another device, by mapping it onto the inner
+//       clock from the last two sync points, and runs freely between them. A sync behind the time
+//       already returned holds the clock until the timeline catches up, so time never runs backwards
+#[derive(Debug)]
+pub struct ExternalClock<C: Clock> {
+    inner: C,
+    anchor: Cell<Option<(f64, f64)>>,
+    rate: Cell<f64>,
+    last: Cell<f64>,
+}
+
+impl<C: Clock> ExternalClock<C> {
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner,
+            anchor: Cell::new(None),
+            rate: Cell::new(1.0),
+            last: Cell::new(f64::NEG_INFINITY),
+        }
+    }
+
+    #[inline]
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    #[inline]
+    pub fn rate(&self) -> f64 {
+        self.rate.get()
+    }
+
+    pub fn sync(&self, external_time: f64) {
+        let inner_time = self.inner.current_time();
+        if let Some((previous_inner, previous_external)) = self.anchor.get() {
+            let elapsed = inner_time - previous_inner;
+            let external_elapsed = external_time - previous_external;
+            if elapsed > 0.0 && external_elapsed > 0.0 {
+                self.rate.set(external_elapsed / elapsed);
+            }
+        }
+        self.anchor.set(Some((inner_time, external_time)));
+    }
+
+    // NOTE: Drops the external timeline, the inner time is returned as is even if it is behind
+    #[inline]
+    pub fn reset(&self) {
+        self.anchor.set(None);
+        self.rate.set(1.0);
+        self.last.set(f64::NEG_INFINITY);
+    }
+}
+
+impl<C: Clock> Clock for ExternalClock<C> {
+    fn current_time(&self) -> f64 {
+        let inner_time = self.inner.current_time();
+        let time = match self.anchor.get() {
+            Some((anchor_inner, anchor_external)) => {
+                anchor_external + (inner_time - anchor_inner) * self.rate.get()
+            }
+            None => inner_time,
+        };
+        let time = time.max(self.last.get());
+        self.last.set(time);
+        time
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manual_clock() {
+        let clock = ManualClock::new(1.0);
+        let shared = clock.clone();
+        clock.advance(0.5);
+        assert_eq!(shared.current_time(), 1.5);
+        shared.set(3.0);
+        assert_eq!(clock.current_time(), 3.0);
+
+        let boxed: Rc<dyn Clock> = Rc::new(clock.clone());
+        assert_eq!(boxed.current_time(), 3.0);
+    }
+
+    #[test]
+    fn test_external_clock() {
+        let inner = ManualClock::new(10.0);
+        let clock = ExternalClock::new(inner.clone());
+        assert_eq!(clock.current_time(), 10.0);
+
+        clock.sync(100.0);
+        inner.advance(1.0);
+        assert_eq!(clock.current_time(), 101.0);
+
+        // NOTE: The external timeline runs 10% fast
+        clock.sync(101.1);
+        assert!((clock.rate() - 1.1).abs() < 1e-9);
+        inner.advance(1.0);
+        assert!((clock.current_time() - 102.2).abs() < 1e-9);
+
+        clock.reset();
+        assert_eq!(clock.current_time(), 12.0);
+    }
+
+    #[test]
+    fn test_external_clock_monotonic() {
+        let inner = ManualClock::new(0.0);
+        let clock = ExternalClock::new(inner.clone());
+        clock.sync(100.0);
+        inner.advance(1.0);
+        assert_eq!(clock.current_time(), 101.0);
+
+        // NOTE: The external timeline ran 20% slow, the clock holds until it passes 101 again
+        clock.sync(100.8);
+        assert_eq!(clock.current_time(), 101.0);
+        inner.advance(0.1);
+        assert_eq!(clock.current_time(), 101.0);
+        inner.advance(0.3);
+        assert!((clock.current_time() - 101.12).abs() < 1e-9);
+    }
+}
@@ -1,6 +1,10 @@
 use web_sys::OscillatorNode;
 
-use crate::{result::Result, unit::Frequency};
+use crate::{
+    result::Result,
+    theory::{Note, Tuning},
+    unit::Frequency,
+};
 
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -42,6 +46,28 @@ impl PitchEnvelope {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_tuning(
+        tuning: &Tuning,
+        initial: Note,
+        peak: Note,
+        attack: f64,
+        decay: f64,
+        sustain: Note,
+        release: f64,
+        end: Note,
+    ) -> Self {
+        Self::new(
+            tuning.freq(&initial),
+            tuning.freq(&peak),
+            attack,
+            decay,
+            tuning.freq(&sustain),
+            release,
+            tuning.freq(&end),
+        )
+    }
+
     #[inline]
     pub fn initial(&self) -> Frequency {
         self.initial
@@ -0,0 +1,195 @@
+use crate::line_error::LineError;
+
+// NOTE: Offsets are fractions of a step (-0.5 to 0.5) and velocities are gains (0.0 to 1.0),
+//       both cycled over the steps of a page
+#[derive(Debug, Clone, PartialEq)]
+pub struct Groove {
+    offsets: Vec<f64>,
+    velocities: Vec<f32>,
+}
+
+impl Groove {
+    pub const MIN_SWING: f32 = 0.5;
+    pub const MAX_SWING: f32 = 0.75;
+    const MAX_OFFSET: f64 = 0.5;
+
+    pub fn new(offsets: Vec<f64>, velocities: Vec<f32>) -> Option<Self> {
+        if offsets.is_empty()
+            || offsets.len() != velocities.len()
+            || offsets
+                .iter()
+                .any(|offset| !(-Self::MAX_OFFSET..=Self::MAX_OFFSET).contains(offset))
+            || velocities
+                .iter()
+                .any(|velocity| !(0.0..=1.0).contains(velocity))
+        {
+            return None;
+        }
+        Some(Self {
+            offsets,
+            velocities,
+        })
+    }
+
+    #[inline]
+    pub fn straight() -> Self {
+        Self {
+            offsets: vec![0.0],
+            velocities: vec![1.0],
+        }
+    }
+
+    // NOTE: The amount is the share of a step pair taken by the on-beat, e.g. 2/3 for triplet swing
+    pub fn swing(amount: f32) -> Option<Self> {
+        if !(Self::MIN_SWING..=Self::MAX_SWING).contains(&amount) {
+            return None;
+        }
+        Self::new(vec![0.0, amount as f64 * 2.0 - 1.0], vec![1.0, 1.0])
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self, LineError> {
+        let s = String::from_utf8_lossy(data);
+        let mut offsets = vec![];
+        let mut velocities = vec![];
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut tokens = line.split_whitespace();
+            let offset: f64 = tokens
+                .next()
+                .and_then(|token| token.parse().ok())
+                .ok_or_else(|| LineError::new(i + 1, format!("invalid offset: {line}")))?;
+            if !(-Self::MAX_OFFSET..=Self::MAX_OFFSET).contains(&offset) {
+                return Err(LineError::new(
+                    i + 1,
+                    "offset should be between -0.5 and 0.5",
+                ));
+            }
+            let velocity: f32 = match tokens.next() {
+                Some(token) => token
+                    .parse()
+                    .map_err(|_| LineError::new(i + 1, format!("invalid velocity: {token}")))?,
+                None => 1.0,
+            };
+            if !(0.0..=1.0).contains(&velocity) {
+                return Err(LineError::new(
+                    i + 1,
+                    "velocity should be between 0.0 and 1.0",
+                ));
+            }
+
+            offsets.push(offset);
+            velocities.push(velocity);
+        }
+
+        if offsets.is_empty() {
+            return Err(LineError::new(s.lines().count() + 1, "missing steps"));
+        }
+        Ok(Self {
+            offsets,
+            velocities,
+        })
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    #[inline]
+    pub fn offset(&self, step: usize) -> f64 {
+        self.offsets[step % self.offsets.len()]
+    }
+
+    #[inline]
+    pub fn velocity(&self, step: usize) -> f32 {
+        self.velocities[step % self.velocities.len()]
+    }
+}
+
+impl Default for Groove {
+    #[inline]
+    fn default() -> Self {
+        Self::straight()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_swing() {
+        let groove = Groove::swing(0.5).unwrap();
+        assert_eq!(groove.offset(1), 0.0);
+
+        let groove = Groove::swing(0.75).unwrap();
+        assert_eq!(groove.offset(0), 0.0);
+        assert_eq!(groove.offset(1), 0.5);
+        assert_eq!(groove.offset(2), 0.0);
+        assert_eq!(groove.offset(3), 0.5);
+
+        assert!(Groove::swing(0.49).is_none());
+        assert!(Groove::swing(0.8).is_none());
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(Groove::new(vec![0.0, 0.1], vec![1.0, 0.5]).is_some());
+        assert!(Groove::new(vec![0.0, 0.1], vec![1.0]).is_none());
+        assert!(Groove::new(vec![0.6], vec![1.0]).is_none());
+        assert!(Groove::new(vec![0.0], vec![1.5]).is_none());
+        assert!(Groove::new(vec![], vec![]).is_none());
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        let data = b"# MPC-ish 4 step groove
+0.0 1.0
+0.1 0.6
+
+-0.05 0.8 # pushed
+0.2
+";
+        let groove = Groove::from_bytes(data).unwrap();
+        assert_eq!(groove.len(), 4);
+        assert_eq!(groove.offset(1), 0.1);
+        assert_eq!(groove.velocity(1), 0.6);
+        assert_eq!(groove.offset(2), -0.05);
+        assert_eq!(groove.velocity(3), 1.0);
+        assert_eq!(groove.offset(5), 0.1);
+    }
+
+    #[test]
+    fn test_from_bytes_errors() {
+        assert_eq!(
+            Groove::from_bytes(b"0.0 1.0\nfoo\n").unwrap_err(),
+            LineError::new(2, "invalid offset: foo")
+        );
+        assert_eq!(
+            Groove::from_bytes(b"0.0 1.0\n0.7\n").unwrap_err(),
+            LineError::new(2, "offset should be between -0.5 and 0.5")
+        );
+        assert_eq!(
+            Groove::from_bytes(b"0.0 loud\n").unwrap_err(),
+            LineError::new(1, "invalid velocity: loud")
+        );
+        assert_eq!(
+            Groove::from_bytes(b"0.0 2\n").unwrap_err(),
+            LineError::new(1, "velocity should be between 0.0 and 1.0")
+        );
+        assert_eq!(
+            Groove::from_bytes(b"# empty\n").unwrap_err(),
+            LineError::new(2, "missing steps")
+        );
+    }
+}
@@ -0,0 +1,20 @@
+use web_sys::{AudioContext, AudioNode};
+
+use crate::{result::Result, theory::Note};
+
+// NOTE: Velocity is a gain (0.0 to 1.0) and duration is the gate length in seconds
+pub trait Instrument {
+    fn play(&self, time: f64, note: &Note, velocity: f32, duration: f64) -> Result<()>;
+}
+
+pub(crate) fn connect_with_velocity(
+    ctx: &AudioContext,
+    node: &AudioNode,
+    velocity: f32,
+) -> Result<()> {
+    let gain = ctx.create_gain()?;
+    gain.gain().set_value(velocity);
+    node.connect_with_audio_node(&gain)?;
+    gain.connect_with_audio_node(&ctx.destination())?;
+    Ok(())
+}
@@ -1,6 +1,11 @@
 pub mod arps;
+pub mod arrangement;
+pub mod clock;
 pub mod envs;
+pub mod groove;
+pub mod instrument;
 pub mod interval;
+pub mod line_error;
 pub mod log;
 pub mod machines;
 pub mod noise;
@@ -9,7 +14,11 @@ pub mod result;
 pub mod sampler;
 pub mod sequencer;
 pub mod songs;
+pub mod sync;
 pub mod synthesizer;
+pub mod tempo;
 pub mod theory;
+pub mod track;
+pub mod transport;
 pub mod unit;
 pub mod worker;
@@ -0,0 +1,44 @@
+use std::fmt::Display;
+
+use wasm_bindgen::JsValue;
+
+// NOTE: A parse error in a line based text format, e.g. Scala files or groove templates
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineError {
+    line: usize,
+    message: String,
+}
+
+impl LineError {
+    pub fn new<S: Into<String>>(line: usize, message: S) -> Self {
+        Self {
+            line,
+            message: message.into(),
+        }
+    }
+
+    #[inline]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for LineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for LineError {}
+
+impl From<LineError> for JsValue {
+    #[inline]
+    fn from(value: LineError) -> Self {
+        JsValue::from_str(&value.to_string())
+    }
+}
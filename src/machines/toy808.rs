@@ -2,20 +2,30 @@ use web_sys::{AudioContext, BiquadFilterNode, BiquadFilterType, GainNode, Oscill
 
 use crate::{
     envs::{AmpEnvelope, PitchEnvelope},
+    instrument::{self, Instrument},
     noise::Noise,
     result::Result,
-    theory::Note,
+    theory::{Note, Tuning},
 };
 
 #[derive(Debug, Clone)]
 pub struct Toy808 {
     ctx: AudioContext,
+    tuning: Tuning,
 }
 
 #[allow(dead_code)]
 impl Toy808 {
+    // NOTE: Sounds follow the General MIDI drum map
+    pub const BD: Note = Note::C2;
+    pub const SD: Note = Note::D2;
+
     pub fn new(ctx: AudioContext) -> Self {
-        Self { ctx }
+        Self::with_tuning(ctx, Tuning::default())
+    }
+
+    pub fn with_tuning(ctx: AudioContext, tuning: Tuning) -> Self {
+        Self { ctx, tuning }
     }
 
     pub fn bd(&self, time: f64) -> Result<BiquadFilterNode> {
@@ -30,8 +40,16 @@ impl Toy808 {
         osc.start_with_when(time)?;
         osc.stop_with_when(time + duration)?;
 
-        let pitch_env =
-            PitchEnvelope::new(Note::A1, Note::A2, attack, decay, Note::A1, 0.0, Note::A1);
+        let pitch_env = PitchEnvelope::with_tuning(
+            &self.tuning,
+            Note::A1,
+            Note::A2,
+            attack,
+            decay,
+            Note::A1,
+            0.0,
+            Note::A1,
+        );
         pitch_env.attach(&mut osc, time, duration)?;
 
         let amp_env = AmpEnvelope::new(self.ctx.clone(), volume, attack, decay, 0.0, 0.0);
@@ -60,7 +78,8 @@ impl Toy808 {
         low_osc.start_with_when(time)?;
         low_osc.stop_with_when(time + duration)?;
 
-        let low_pitch_env = PitchEnvelope::new(
+        let low_pitch_env = PitchEnvelope::with_tuning(
+            &self.tuning,
             Note::C2,
             Note::C3,
             attack,
@@ -76,7 +95,8 @@ impl Toy808 {
         high_osc.start_with_when(time)?;
         high_osc.stop_with_when(time + duration)?;
 
-        let high_pitch_env = PitchEnvelope::new(
+        let high_pitch_env = PitchEnvelope::with_tuning(
+            &self.tuning,
             Note::C3,
             Note::C4,
             attack,
@@ -120,3 +140,19 @@ impl Toy808 {
         Ok(output)
     }
 }
+
+impl Instrument for Toy808 {
+    fn play(&self, time: f64, note: &Note, velocity: f32, _duration: f64) -> Result<()> {
+        match *note {
+            Self::BD => {
+                let bd = self.bd(time)?;
+                instrument::connect_with_velocity(&self.ctx, &bd, velocity)
+            }
+            Self::SD => {
+                let sd = self.sd(time)?;
+                instrument::connect_with_velocity(&self.ctx, &sd, velocity)
+            }
+            _ => Ok(()),
+        }
+    }
+}
@@ -1,14 +1,31 @@
-use std::{cell::RefCell, panic, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    panic,
+    rc::Rc,
+};
 
 use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::js_sys::Function;
 
-use crate::{result::Result, songs::Song, worker::WebWorker};
+use crate::{
+    result::Result,
+    sequencer::{CatchUp, Sequencer},
+    songs::Song,
+    sync::TapTempo,
+    tempo::Curve,
+    theory::{Duration, NoteValue},
+    transport::Transport,
+    worker::WebWorker,
+};
 
 #[wasm_bindgen]
 pub struct Player {
     song: Option<Rc<RefCell<Song>>>,
     worker: WebWorker,
-    is_playing: bool,
+    is_playing: Rc<Cell<bool>>,
+    onended: Option<Function>,
+    tap_tempo: TapTempo,
+    catch_up: CatchUp,
 }
 
 #[wasm_bindgen]
@@ -21,27 +38,56 @@ impl Player {
 
         Ok(Self {
             song: None,
-            is_playing: false,
+            is_playing: Rc::new(Cell::new(false)),
             worker,
+            onended: None,
+            tap_tempo: TapTempo::new(),
+            catch_up: CatchUp::default(),
         })
     }
 
     pub fn set_song(&mut self, song: Song) -> Result<()> {
         self.stop()?;
         self.song = Some(Rc::new(RefCell::new(song)));
+        self.apply_catch_up_policy();
         Ok(())
     }
 
+    // NOTE: The callback is called once the last step of a finished song has played, as the worker
+    //       keeps ticking until the AudioContext time passes its end
+    pub fn set_onended(&mut self, f: Option<Function>) {
+        self.onended = f;
+    }
+
     pub fn play(&mut self) -> Result<()> {
         if let Some(song_ref) = self.song.clone() {
+            let handle = self.worker.handle().clone();
+            let is_playing = self.is_playing.clone();
+            let onended = self.onended.clone();
             self.worker.set_onmessage(move |message| {
-                if message.data() == "tick" {
-                    song_ref.borrow_mut().tick().expect("should tick");
+                if message.data() != "tick" || !is_playing.get() {
+                    return;
+                }
+
+                // NOTE: Releases the song before onended, which may call back into the player
+                let is_finished = {
+                    let mut song = song_ref.borrow_mut();
+                    song.tick().expect("should tick");
+                    song.is_finished()
+                };
+                if is_finished {
+                    handle
+                        .post_message(&JsValue::from_str("stop"))
+                        .expect("should stop worker");
+                    is_playing.set(false);
+                    if let Some(f) = &onended {
+                        f.call0(&JsValue::NULL).expect("should call onended");
+                    }
                 }
             });
 
             self.worker.post_message("start")?;
-            self.is_playing = true;
+            self.is_playing.set(true);
         }
 
         Ok(())
@@ -49,13 +95,125 @@ impl Player {
 
     pub fn stop(&mut self) -> Result<()> {
         self.worker.post_message("stop")?;
-        self.is_playing = false;
+        self.is_playing.set(false);
 
         Ok(())
     }
 
     pub fn is_playing(&self) -> bool {
-        self.is_playing
+        self.is_playing.get()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.song
+            .as_ref()
+            .is_some_and(|song| song.borrow_mut().is_finished())
+    }
+
+    // NOTE: Takes the AudioContext time, e.g. from requestAnimationFrame, to draw a playhead
+    pub fn transport(&self, current_time: f64) -> Option<Transport> {
+        let song = self.song.as_ref()?;
+        let mut song = song.borrow_mut();
+        song.sequencer()?.transport(current_time)
+    }
+
+    pub fn bpm(&self) -> Option<f32> {
+        let song = self.song.as_ref()?;
+        let mut song = song.borrow_mut();
+        Some(song.sequencer()?.bpm())
+    }
+
+    pub fn set_bpm(&mut self, bpm: f32) -> Result<()> {
+        self.ramp_bpm(bpm, 0, Curve::Instant)
+    }
+
+    // NOTE: Sets the tempo once two taps are in, timestamps are in seconds, e.g. the AudioContext time
+    pub fn tap(&mut self, timestamp: f64) -> Result<Option<f32>> {
+        let Some(bpm) = self.tap_tempo.tap(timestamp) else {
+            return Ok(None);
+        };
+        self.set_bpm(bpm)?;
+        Ok(Some(bpm))
+    }
+
+    // NOTE: Kept for the songs set later, and applied to the current one if any
+    pub fn set_catch_up_policy(&mut self, catch_up: CatchUp) -> Result<()> {
+        self.catch_up = catch_up;
+        self.apply_catch_up_policy();
+        Ok(())
+    }
+
+    pub fn catch_up_policy(&self) -> CatchUp {
+        self.catch_up
+    }
+
+    pub fn late_steps(&self) -> usize {
+        self.song
+            .as_ref()
+            .and_then(|song| Some(song.borrow_mut().sequencer()?.late_steps()))
+            .unwrap_or_default()
+    }
+
+    pub fn dropped_steps(&self) -> usize {
+        self.song
+            .as_ref()
+            .and_then(|song| Some(song.borrow_mut().sequencer()?.dropped_steps()))
+            .unwrap_or_default()
+    }
+
+    pub fn set_midi_sync(&mut self, enabled: bool) -> Result<()> {
+        self.with_sequencer(|sequencer| {
+            sequencer.set_midi_sync(enabled);
+            Ok(())
+        })
+    }
+
+    // NOTE: Takes the bytes of a Web MIDI message with its timestamp converted to the AudioContext time
+    pub fn receive_midi(&mut self, bytes: &[u8], timestamp: f64) -> Result<()> {
+        self.with_sequencer(|sequencer| {
+            sequencer.receive_midi(bytes, timestamp);
+            Ok(())
+        })
+    }
+
+    // NOTE: Ramps from the next step over the given quarter-note beats, e.g. for a tempo slider
+    pub fn ramp_bpm(&mut self, bpm: f32, beats: u32, curve: Curve) -> Result<()> {
+        if !(bpm.is_finite() && bpm > 0.0) {
+            return Err(JsValue::from_str("bpm should be positive"));
+        }
+
+        let length: NoteValue = Duration::Quarter.note_value() * beats as u64;
+        self.with_sequencer(|sequencer| {
+            sequencer
+                .ramp_bpm(bpm, length, curve)
+                .ok_or_else(|| JsValue::from_str("bpm should be positive"))
+        })
+    }
+}
+
+impl Player {
+    // NOTE: Songs without a sequencer have no steps to catch up on
+    fn apply_catch_up_policy(&self) {
+        let Some(song) = self.song.as_ref() else {
+            return;
+        };
+        if let Some(sequencer) = song.borrow_mut().sequencer() {
+            sequencer.set_catch_up_policy(self.catch_up);
+        }
+    }
+
+    fn with_sequencer<F>(&self, f: F) -> Result<()>
+    where
+        F: FnOnce(&mut Sequencer) -> Result<()>,
+    {
+        let Some(song) = self.song.as_ref() else {
+            return Ok(());
+        };
+        let mut song = song.borrow_mut();
+        let sequencer = song
+            .sequencer()
+            .ok_or_else(|| JsValue::from_str("song has no sequencer"))?;
+        f(sequencer)
     }
 }
 
@@ -64,7 +222,12 @@ pub mod tests {
     use wasm_bindgen_test::*;
 
     use super::*;
-    use crate::songs::Playable;
+    use crate::{
+        clock::ManualClock,
+        songs::{metronome::Metronome, Playable},
+        theory::TimeSignature,
+        track::tests::{Events, Recorder},
+    };
 
     struct TestSong {}
 
@@ -145,4 +308,65 @@ pub mod tests {
         player.stop().unwrap();
         assert!(!player.is_playing());
     }
+
+    #[wasm_bindgen_test]
+    pub fn test_is_finished() {
+        let mut player = Player::new().unwrap();
+        assert!(!player.is_finished());
+        let song = TestSong::new().into();
+        player.set_song(song).unwrap();
+        player.play().unwrap();
+        assert!(!player.is_finished());
+        assert!(player.is_playing());
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_transport() {
+        let mut player = Player::new().unwrap();
+        assert_eq!(player.transport(0.0), None);
+        let song = TestSong::new().into();
+        player.set_song(song).unwrap();
+        assert_eq!(player.transport(0.0), None);
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_catch_up() {
+        let mut player = Player::new().unwrap();
+        player.set_catch_up_policy(CatchUp::Skip).unwrap();
+        assert_eq!(player.late_steps(), 0);
+        assert_eq!(player.dropped_steps(), 0);
+
+        // NOTE: The policy set before the song reaches its sequencer
+        let clock = ManualClock::new(0.0);
+        let events = Events::default();
+        let metronome = Metronome::with_instrument(
+            Rc::new(clock.clone()),
+            60.0,
+            TimeSignature::default(),
+            Recorder::new(&events),
+        );
+        player.set_song(metronome.into()).unwrap();
+        assert_eq!(player.catch_up_policy(), CatchUp::Skip);
+
+        let song = player.song.clone().unwrap();
+        song.borrow_mut().tick().unwrap();
+        clock.advance(3.5);
+        song.borrow_mut().tick().unwrap();
+        assert_eq!(player.late_steps(), 0);
+        assert_eq!(player.dropped_steps(), 3);
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_set_bpm() {
+        let mut player = Player::new().unwrap();
+        player.set_bpm(120.0).unwrap();
+        assert_eq!(player.bpm(), None);
+        assert!(player.set_bpm(0.0).is_err());
+        assert!(player.set_bpm(f32::NAN).is_err());
+        assert!(player.set_bpm(f32::INFINITY).is_err());
+
+        let song = TestSong::new().into();
+        player.set_song(song).unwrap();
+        assert!(player.ramp_bpm(120.0, 4, Curve::Linear).is_err());
+    }
 }
@@ -3,22 +3,42 @@ use std::collections::HashMap;
 use wasm_bindgen_futures::{js_sys::Uint8Array, JsFuture};
 use web_sys::{AudioBuffer, AudioBufferSourceNode, AudioBufferSourceOptions, AudioContext};
 
-use crate::{result::Result, theory::Note};
+use crate::{
+    instrument::{self, Instrument},
+    result::Result,
+    theory::{Note, Tuning},
+};
 
 #[derive(Clone)]
 pub struct MelodicSampler {
     ctx: AudioContext,
     samples: HashMap<Note, AudioBuffer>,
+    tuning: Tuning,
 }
 
 impl MelodicSampler {
     pub fn new(ctx: AudioContext) -> Self {
+        Self::with_tuning(ctx, Tuning::default())
+    }
+
+    pub fn with_tuning(ctx: AudioContext, tuning: Tuning) -> Self {
         Self {
             ctx,
             samples: HashMap::new(),
+            tuning,
         }
     }
 
+    #[inline]
+    pub fn tuning(&self) -> &Tuning {
+        &self.tuning
+    }
+
+    #[inline]
+    pub fn set_tuning(&mut self, tuning: Tuning) {
+        self.tuning = tuning;
+    }
+
     pub async fn insert(&mut self, note: Note, sample_data: &[u8]) -> Result<()> {
         let buffer = Self::buffer(&self.ctx, sample_data).await?;
         self.samples.insert(note, buffer);
@@ -86,8 +106,8 @@ impl MelodicSampler {
             let closest_note = self
                 .find_closest_note_in_samples(note)
                 .expect("closest note should be found");
-            let closest_freq = closest_note.freq();
-            Some((closest_note, note.freq().0 / closest_freq.0))
+            let closest_freq = self.tuning.freq(&closest_note);
+            Some((closest_note, self.tuning.freq(note).0 / closest_freq.0))
         }
     }
 
@@ -104,6 +124,16 @@ impl MelodicSampler {
     }
 }
 
+// NOTE: Samples are one-shots and ring out regardless of the duration
+impl Instrument for MelodicSampler {
+    fn play(&self, time: f64, note: &Note, velocity: f32, _duration: f64) -> Result<()> {
+        let src = self.buffer_node(note)?;
+        instrument::connect_with_velocity(&self.ctx, &src, velocity)?;
+        src.start_with_when(time)?;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use wasm_bindgen_test::*;
@@ -111,6 +141,7 @@ pub mod tests {
     wasm_bindgen_test_configure!(run_in_browser);
 
     use super::*;
+    use crate::theory::{PitchClass, TuningSystem};
 
     const A2: &[u8] = include_bytes!("../samples/a2.m4a").as_slice();
     const A3: &[u8] = include_bytes!("../samples/a3.m4a").as_slice();
@@ -213,6 +244,18 @@ pub mod tests {
         );
     }
 
+    #[wasm_bindgen_test]
+    pub async fn test_calc_note_and_playback_rate_with_tuning() {
+        let ctx = AudioContext::new().unwrap();
+        let tuning = Tuning::new(440.0, TuningSystem::JustIntonation(PitchClass::A));
+        let mut sampler = MelodicSampler::with_tuning(ctx, tuning);
+        sampler.insert(Note::A2, A2).await.unwrap();
+        assert_eq!(
+            sampler.calc_note_and_playback_rate(&Note::E3),
+            Some((Note::A2, 1.5))
+        );
+    }
+
     #[wasm_bindgen_test]
     pub async fn test_calc_note_and_playback_rate_2_contains() {
         let ctx = AudioContext::new().unwrap();
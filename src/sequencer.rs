@@ -1,29 +1,118 @@
-use crate::{result::Result, theory::Duration};
+use std::{
+    cell::{Cell, Ref, RefCell},
+    collections::VecDeque,
+    rc::{Rc, Weak},
+};
+
+use rand::{RngExt, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    arrangement::Arrangement,
+    groove::Groove,
+    result::Result,
+    sync::{MidiClock, MidiMessage},
+    tempo::{Curve, TempoMap},
+    theory::{Accent, Duration, NoteValue, TimeSignature},
+    track::{ConditionContext, Track},
+    transport::Transport,
+};
 
 #[allow(dead_code)]
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Resolution {
+    ThirtySecond,
+    SixteenthTriplet,
+    Sixteenth,
+    EighthTriplet,
     Eighth,
     Quarter,
 }
 
 impl Resolution {
+    // NOTE: Triplet resolutions return the duration they are a tuplet of
     pub fn duration(&self) -> Duration {
         match self {
+            Resolution::ThirtySecond => Duration::ThirtySecond,
+            Resolution::SixteenthTriplet => Duration::Sixteenth,
+            Resolution::Sixteenth => Duration::Sixteenth,
+            Resolution::EighthTriplet => Duration::Eighth,
             Resolution::Eighth => Duration::Eighth,
             Resolution::Quarter => Duration::Quarter,
         }
     }
+
+    #[inline]
+    pub fn is_triplet(&self) -> bool {
+        matches!(
+            self,
+            Resolution::SixteenthTriplet | Resolution::EighthTriplet
+        )
+    }
+
+    pub fn note_value(&self) -> NoteValue {
+        if self.is_triplet() {
+            self.duration()
+                .tuplet(3, 2)
+                .expect("should be triplet of duration")
+        } else {
+            self.duration().note_value()
+        }
+    }
+
+    pub fn from_duration(duration: &Duration) -> Option<Resolution> {
+        match duration {
+            Duration::ThirtySecond => Some(Resolution::ThirtySecond),
+            Duration::Sixteenth => Some(Resolution::Sixteenth),
+            Duration::Eighth => Some(Resolution::Eighth),
+            Duration::Quarter => Some(Resolution::Quarter),
+            _ => None,
+        }
+    }
+}
+
+// NOTE: How steps are played when a tick comes in after their time, e.g. in a throttled background tab,
+//       Burst plays them all at once, Skip drops them, Reanchor shifts the timeline to the current time
+//       and Compress plays them faster until caught up with the timeline
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CatchUp {
+    #[default]
+    Burst,
+    Skip,
+    Reanchor,
+    Compress,
 }
 
 pub struct Sequencer {
-    bpm: f32,
+    tempo: Rc<RefCell<TempoMap>>,
+    playheads: Rc<RefCell<Vec<Weak<Cell<NoteValue>>>>>,
     pages: usize,
     resolution: Resolution,
+    time_signatures: Vec<TimeSignature>,
+    groove: Groove,
+    tracks: Vec<Track>,
+    rng: ChaCha8Rng,
+    fill: bool,
+    arrangement: Option<Arrangement>,
+    finished: bool,
+    midi_clock: Option<MidiClock>,
+    midi_bpm: Rc<Cell<Option<f32>>>,
+    catch_up: CatchUp,
+    catch_up_time: Option<f64>,
+    late_steps: usize,
+    dropped_steps: usize,
     interval: u32,
     step: usize,
     page: usize,
+    count: usize,
+    bar: usize,
+    history: VecDeque<Transport>,
+    position: Rc<Cell<NoteValue>>,
+    start_time: f64,
     beat_time: f64,
+    current_time: f64,
 }
 
 impl Sequencer {
@@ -34,61 +123,629 @@ impl Sequencer {
         current_time: f64,
         interval: u32,
     ) -> Self {
+        let position = Rc::new(Cell::new(NoteValue::ZERO));
         Self {
-            bpm,
+            tempo: Rc::new(RefCell::new(TempoMap::new(bpm))),
+            playheads: Rc::new(RefCell::new(vec![Rc::downgrade(&position)])),
             pages,
             resolution,
+            time_signatures: vec![TimeSignature::default(); pages],
+            groove: Groove::default(),
+            tracks: vec![],
+            rng: ChaCha8Rng::seed_from_u64(0),
+            fill: false,
+            arrangement: None,
+            finished: false,
+            midi_clock: None,
+            midi_bpm: Rc::new(Cell::new(None)),
+            catch_up: CatchUp::default(),
+            catch_up_time: None,
+            late_steps: 0,
+            dropped_steps: 0,
             interval,
             step: 0,
             page: 0,
+            count: 0,
+            bar: 0,
+            history: VecDeque::new(),
+            position,
+            start_time: current_time,
             beat_time: current_time,
+            current_time,
         }
     }
 
+    // NOTE: The sibling shares the tempo map and MIDI tempo, so tempo changes reach both, and starts
+    //       at its first step from the position of this sequencer on, timed from its beat time, as
+    //       the tempo map may no longer hold the changes before that position
+    pub fn with_resolution(&self, resolution: Resolution) -> Sequencer {
+        let mut sequencer = Self {
+            resolution,
+            tempo: Rc::clone(&self.tempo),
+            playheads: Rc::clone(&self.playheads),
+            midi_bpm: Rc::clone(&self.midi_bpm),
+            time_signatures: self.time_signatures.clone(),
+            ..Self::new(
+                self.tempo.borrow().initial_bpm(),
+                self.pages,
+                Resolution::Quarter,
+                self.start_time,
+                self.interval,
+            )
+        };
+        sequencer.current_time = self.current_time;
+        self.playheads
+            .borrow_mut()
+            .push(Rc::downgrade(&sequencer.position));
+        if let Some(arrangement) = &self.arrangement {
+            sequencer.set_arrangement(arrangement.clone());
+        }
+        while !sequencer.finished && sequencer.position() < self.position() {
+            sequencer.step_forward();
+        }
+        sequencer.beat_time = self.beat_time + self.seconds_between(&sequencer.position());
+        sequencer
+    }
+
+    #[inline]
     pub fn tick<F>(&mut self, current_time: f64, mut f: F) -> Result<()>
     where
         F: FnMut(f64, usize, usize) -> Result<()>,
     {
-        let beats_per_measure = self.resolution.duration().beats_per_measure();
-        let seconds_per_beat = self.seconds_per_beat();
+        self.tick_with_velocity(current_time, |time, page, step, _velocity| {
+            f(time, page, step)
+        })
+    }
+
+    pub fn tick_with_velocity<F>(&mut self, current_time: f64, mut f: F) -> Result<()>
+    where
+        F: FnMut(f64, usize, usize, f32) -> Result<()>,
+    {
         let interval = self.interval as f64 / 1000.0; // in secs
+        self.current_time = current_time;
+
+        // NOTE: Keeps the steps scheduled ahead and the one heard now for the transport
+        while self
+            .history
+            .get(1)
+            .is_some_and(|transport| transport.time() <= current_time)
+        {
+            self.history.pop_front();
+        }
+
+        if let Some(midi_clock) = &self.midi_clock {
+            if !midi_clock.is_running() {
+                return Ok(());
+            }
+            // NOTE: Waits for enough pulses after a start to estimate tempo and phase
+            let (Some(bpm), Some(time)) = (midi_clock.bpm(), midi_clock.time_at(&self.position()))
+            else {
+                return Ok(());
+            };
+            self.midi_bpm.set(Some(bpm));
+            self.beat_time = time;
+        }
 
         let next_time = current_time + interval;
-        while self.beat_time < next_time {
+        while !self.finished && self.beat_time < next_time {
+            let offset = self.groove.offset(self.step) * self.seconds_per_beat();
+            let velocity = self.groove.velocity(self.step);
+
             // NOTE: Added interval as an offset for the first beat
-            f(self.beat_time + interval, self.page, self.step)?;
+            let Some(time) = self.catch_up(current_time, self.beat_time + interval, offset) else {
+                self.advance();
+                continue;
+            };
+            f(time, self.page, self.step, velocity)?;
+            self.trigger(time, velocity)?;
+            self.history.push_back(Transport::new(
+                self.bar,
+                self.beat(self.page, self.step),
+                self.page,
+                self.step,
+                time,
+            ));
+
+            self.advance();
+        }
+
+        Ok(())
+    }
+
+    // NOTE: Returns the time to play the step at, or None when it is dropped
+    fn catch_up(&mut self, current_time: f64, time: f64, offset: f64) -> Option<f64> {
+        const COMPRESSION: f64 = 4.0;
+
+        let is_late = time < current_time;
+        if self.catch_up != CatchUp::Compress && is_late {
+            if self.catch_up == CatchUp::Skip {
+                self.dropped_steps += 1;
+                return None;
+            }
+            self.late_steps += 1;
+        }
 
-            self.beat_time += seconds_per_beat;
-            self.step = (self.step + 1) % beats_per_measure;
-            if self.step == 0 {
-                self.page = (self.page + 1) % self.pages;
+        match self.catch_up {
+            CatchUp::Burst | CatchUp::Skip => Some(time + offset),
+            CatchUp::Reanchor if is_late => {
+                // NOTE: Schedules ahead by the interval as if the sequencer started now, and moves
+                //       the start along so the song time stays on the shifted timeline
+                let shift = current_time - self.beat_time;
+                self.beat_time = current_time;
+                self.start_time += shift;
+                Some(time + shift + offset)
+            }
+            CatchUp::Reanchor => Some(time + offset),
+            // NOTE: Missed steps play from the current time on, scheduled ahead by the interval, and
+            //       steps on time wait behind them until the compressed steps are back on the timeline
+            CatchUp::Compress => {
+                let interval = self.interval as f64 / 1000.0; // in secs
+                let compressed = match self.catch_up_time {
+                    Some(catch_up_time) if catch_up_time > time => {
+                        catch_up_time.max(current_time + interval)
+                    }
+                    _ if is_late => current_time + interval,
+                    _ => {
+                        self.catch_up_time = None;
+                        return Some(time + offset);
+                    }
+                };
+                let seconds = self.seconds(&self.step_length(self.page, self.step));
+                self.catch_up_time = Some(compressed + seconds / COMPRESSION);
+                self.late_steps += 1;
+                Some(compressed + offset)
             }
         }
+    }
+
+    // NOTE: Conditions are evaluated on muted tracks too, so Pre and Nei do not change with mutes
+    fn trigger(&mut self, time: f64, velocity: f32) -> Result<()> {
+        let soloed = self.tracks.iter().any(Track::is_soloed);
+        let step_length = self.step_length(self.page, self.step);
+        let midi_bpm = self.midi_bpm.get();
+        let position = self.position();
+        let tempo = self.tempo.borrow();
+        let mut neighbor = false;
+
+        for track in self.tracks.iter_mut() {
+            let is_met = match track.step(self.count) {
+                Some(step) if step.is_conditional() => {
+                    let context = ConditionContext {
+                        iteration: self.count / track.len(),
+                        fill: self.fill,
+                        previous: track.last_condition(),
+                        neighbor,
+                    };
+                    Some(
+                        step.condition()
+                            .is_none_or(|condition| condition.is_met(&context))
+                            && (step.probability() >= 1.0
+                                || self.rng.random::<f32>() < step.probability()),
+                    )
+                }
+                _ => None,
+            };
+            if let Some(is_met) = is_met {
+                track.set_last_condition(is_met);
+            }
+            neighbor = track.last_condition();
 
+            let Some(step) = track.step(self.count) else {
+                continue;
+            };
+            if is_met == Some(false) || track.is_muted() || (soloed && !track.is_soloed()) {
+                continue;
+            }
+
+            let length = step.length().cloned().unwrap_or(step_length);
+            let duration = match midi_bpm {
+                Some(bpm) => length.seconds(bpm),
+                None => tempo.seconds(&position, &(position + length)),
+            };
+            track
+                .instrument()
+                .play(time, step.note(), velocity * step.velocity(), duration)?;
+        }
         Ok(())
     }
 
+    // NOTE: Synced sequencers run at the MIDI clock tempo, and return to the tempo map once sync is off
+    #[inline]
+    fn seconds(&self, length: &NoteValue) -> f64 {
+        self.seconds_between(&(self.position() + *length))
+    }
+
+    // NOTE: Seconds from the position of this sequencer to a later one
+    fn seconds_between(&self, position: &NoteValue) -> f64 {
+        match self.midi_bpm.get() {
+            Some(bpm) => position
+                .checked_sub(&self.position())
+                .map_or(0.0, |length| length.seconds(bpm)),
+            None => self.tempo.borrow().seconds(&self.position(), position),
+        }
+    }
+
+    #[inline]
+    fn position(&self) -> NoteValue {
+        self.position.get()
+    }
+
+    fn advance(&mut self) {
+        let length = self.step_length(self.page, self.step);
+        self.beat_time += self.seconds(&length);
+        self.step_forward();
+    }
+
+    fn step_forward(&mut self) {
+        let length = self.step_length(self.page, self.step);
+        self.position.set(self.position() + length);
+        self.count += 1;
+        self.step = (self.step + 1) % self.steps_per_page(self.page);
+        if self.step == 0 {
+            self.bar += 1;
+            self.page = match self.arrangement.as_mut() {
+                Some(arrangement) => match arrangement.next_pattern() {
+                    Some(page) => page,
+                    None => {
+                        self.finished = true;
+                        self.page
+                    }
+                },
+                None => (self.page + 1) % self.pages,
+            };
+        }
+    }
+
+    // NOTE: The step heard at the current time, which is behind the steps scheduled ahead by the interval
+    pub fn transport(&self, current_time: f64) -> Option<Transport> {
+        let interval = self.interval as f64 / 1000.0; // in secs
+        let index = self
+            .history
+            .iter()
+            .rposition(|transport| transport.time() <= current_time)?;
+        let next_time = self
+            .history
+            .get(index + 1)
+            .map(Transport::time)
+            .unwrap_or(self.beat_time + interval);
+        let song_time = (current_time - self.start_time - interval).max(0.0);
+        Some(self.history[index].at(song_time, next_time))
+    }
+
+    fn beat(&self, page: usize, step: usize) -> usize {
+        let elapsed = self.resolution.note_value() * step as u64;
+        let units = elapsed
+            .checked_div(&self.time_signature(page).unit().note_value())
+            .expect("should have non-zero unit");
+        (units.numerator() / units.denominator()) as usize
+    }
+
     #[allow(dead_code)]
     #[inline]
     pub fn resolution(&self) -> Resolution {
         self.resolution.clone()
     }
 
+    #[inline]
+    pub fn groove(&self) -> &Groove {
+        &self.groove
+    }
+
+    #[inline]
+    pub fn set_groove(&mut self, groove: Groove) {
+        self.groove = groove;
+    }
+
+    #[inline]
+    pub fn tracks(&self) -> &[Track] {
+        &self.tracks
+    }
+
+    #[inline]
+    pub fn track(&self, name: &str) -> Option<&Track> {
+        self.tracks.iter().find(|track| track.name() == name)
+    }
+
+    #[inline]
+    pub fn track_mut(&mut self, name: &str) -> Option<&mut Track> {
+        self.tracks.iter_mut().find(|track| track.name() == name)
+    }
+
+    // NOTE: Replaces the track with the same name, if any
+    pub fn add_track(&mut self, track: Track) {
+        match self.track_mut(track.name()) {
+            Some(existing) => *existing = track,
+            None => self.tracks.push(track),
+        }
+    }
+
+    pub fn remove_track(&mut self, name: &str) -> Option<Track> {
+        let index = self.tracks.iter().position(|track| track.name() == name)?;
+        Some(self.tracks.remove(index))
+    }
+
+    #[inline]
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = ChaCha8Rng::seed_from_u64(seed);
+    }
+
+    #[inline]
+    pub fn is_fill(&self) -> bool {
+        self.fill
+    }
+
+    #[inline]
+    pub fn set_fill(&mut self, fill: bool) {
+        self.fill = fill;
+    }
+
+    #[inline]
+    pub fn arrangement(&self) -> Option<&Arrangement> {
+        self.arrangement.as_ref()
+    }
+
+    // NOTE: Restarts the arrangement from the next step, which plays the first step of its first pattern
+    pub fn set_arrangement(&mut self, mut arrangement: Arrangement) -> Option<()> {
+        if arrangement
+            .sections()
+            .iter()
+            .flat_map(|section| section.patterns())
+            .any(|pattern| *pattern >= self.pages)
+        {
+            return None;
+        }
+
+        arrangement.restart();
+        match arrangement.next_pattern() {
+            Some(page) => {
+                self.page = page;
+                self.finished = false;
+            }
+            None => self.finished = true,
+        }
+        self.step = 0;
+        self.arrangement = Some(arrangement);
+        Some(())
+    }
+
+    #[inline]
+    pub fn clear_arrangement(&mut self) {
+        self.arrangement = None;
+        self.finished = false;
+    }
+
+    #[inline]
+    pub fn section(&self) -> Option<&str> {
+        self.arrangement.as_ref()?.current_section()
+    }
+
+    // NOTE: Only an arrangement can finish, sequencers without one loop over their pages forever.
+    //       It finishes at the first tick after its last step has played, not the one scheduling it
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.finished && self.current_time >= self.end_time()
+    }
+
+    // NOTE: The end of the last scheduled step, compressed steps may run past the timeline
+    fn end_time(&self) -> f64 {
+        let interval = self.interval as f64 / 1000.0; // in secs
+        let end_time = self.beat_time + interval;
+        self.catch_up_time
+            .map_or(end_time, |catch_up_time| catch_up_time.max(end_time))
+    }
+
+    #[inline]
+    pub fn catch_up_policy(&self) -> CatchUp {
+        self.catch_up
+    }
+
+    #[inline]
+    pub fn set_catch_up_policy(&mut self, catch_up: CatchUp) {
+        self.catch_up = catch_up;
+        self.catch_up_time = None;
+    }
+
+    // NOTE: Steps played after their time, dropped steps are counted separately
+    #[inline]
+    pub fn late_steps(&self) -> usize {
+        self.late_steps
+    }
+
+    #[inline]
+    pub fn dropped_steps(&self) -> usize {
+        self.dropped_steps
+    }
+
+    #[inline]
+    pub fn reset_counters(&mut self) {
+        self.late_steps = 0;
+        self.dropped_steps = 0;
+    }
+
+    #[inline]
+    pub fn midi_clock(&self) -> Option<&MidiClock> {
+        self.midi_clock.as_ref()
+    }
+
+    // NOTE: Synced sequencers follow the tempo and phase of the MIDI clock, and leave the tempo map as is
+    pub fn set_midi_sync(&mut self, enabled: bool) {
+        self.midi_clock = enabled.then(MidiClock::new);
+        self.midi_bpm.set(None);
+    }
+
+    pub fn receive_midi(&mut self, bytes: &[u8], timestamp: f64) {
+        let Some(midi_clock) = self.midi_clock.as_mut() else {
+            return;
+        };
+        if midi_clock
+            .feed(bytes, timestamp)
+            .contains(&MidiMessage::Start)
+        {
+            self.restart();
+        }
+    }
+
+    pub fn restart(&mut self) {
+        self.step = 0;
+        self.page = 0;
+        self.count = 0;
+        self.bar = 0;
+        self.position.set(NoteValue::ZERO);
+        self.history.clear();
+        self.finished = false;
+        if let Some(mut arrangement) = self.arrangement.take() {
+            arrangement.restart();
+            self.set_arrangement(arrangement);
+        }
+    }
+
+    #[inline]
+    pub fn time_signature(&self, page: usize) -> &TimeSignature {
+        &self.time_signatures[page % self.pages]
+    }
+
+    pub fn set_time_signature(&mut self, time_signature: TimeSignature) {
+        self.time_signatures = vec![time_signature; self.pages];
+        self.step %= self.steps_per_page(self.page);
+    }
+
+    pub fn set_page_time_signature(&mut self, page: usize, time_signature: TimeSignature) {
+        self.time_signatures[page % self.pages] = time_signature;
+        self.step %= self.steps_per_page(self.page);
+    }
+
+    #[inline]
+    pub fn steps_per_page(&self, page: usize) -> usize {
+        self.time_signature(page)
+            .steps(&self.resolution.note_value())
+    }
+
+    fn step_length(&self, page: usize, step: usize) -> NoteValue {
+        let length = self.resolution.note_value();
+        let elapsed = length * step as u64;
+        let remaining = self
+            .time_signature(page)
+            .measure()
+            .checked_sub(&elapsed)
+            .unwrap_or(NoteValue::ZERO);
+        length.min(remaining)
+    }
+
+    pub fn accents(&self) -> Vec<Vec<Accent>> {
+        let length = self.resolution.note_value();
+        (0..self.pages)
+            .map(|page| {
+                let time_signature = self.time_signature(page);
+                (0..self.steps_per_page(page))
+                    .map(|step| time_signature.accent(&(length * step as u64)))
+                    .collect()
+            })
+            .collect()
+    }
+
     #[inline]
     pub fn seconds_per_beat(&self) -> f64 {
-        (60.0 / self.bpm as f64) * (4.0 * self.resolution.duration().relative() as f64)
+        self.resolution.note_value().seconds(self.bpm())
     }
 
-    #[allow(dead_code)]
     #[inline]
-    pub fn set_bpm(&mut self, bpm: f32) {
-        self.bpm = bpm;
+    pub fn bpm(&self) -> f32 {
+        self.midi_bpm
+            .get()
+            .unwrap_or_else(|| self.tempo.borrow().bpm_at(&self.position()))
+    }
+
+    #[inline]
+    pub fn tempo(&self) -> Ref<'_, TempoMap> {
+        self.tempo.borrow()
+    }
+
+    // NOTE: Changes the tempo from the next step on, cutting any ramp in progress
+    #[inline]
+    pub fn set_bpm(&mut self, bpm: f32) -> Option<()> {
+        self.schedule_change(self.position(), bpm, NoteValue::ZERO, Curve::Instant)
+    }
+
+    #[inline]
+    pub fn ramp_bpm(&mut self, bpm: f32, length: NoteValue, curve: Curve) -> Option<()> {
+        self.schedule_change(self.position(), bpm, length, curve)
+    }
+
+    pub fn schedule_bpm(&mut self, page: usize, step: usize, bpm: f32) -> Option<()> {
+        let position = self.next_position(page, step)?;
+        self.schedule_change(position, bpm, NoteValue::ZERO, Curve::Instant)
+    }
+
+    pub fn schedule_ramp(
+        &mut self,
+        page: usize,
+        step: usize,
+        bpm: f32,
+        length: NoteValue,
+        curve: Curve,
+    ) -> Option<()> {
+        let position = self.next_position(page, step)?;
+        self.schedule_change(position, bpm, length, curve)
+    }
+
+    // NOTE: Drops the changes behind the playheads of all sequencers sharing the tempo map before
+    //       adding one, so the map does not grow over a long session
+    fn schedule_change(
+        &mut self,
+        position: NoteValue,
+        bpm: f32,
+        length: NoteValue,
+        curve: Curve,
+    ) -> Option<()> {
+        if !(bpm.is_finite() && bpm > 0.0) {
+            return None;
+        }
+
+        let mut playheads = self.playheads.borrow_mut();
+        playheads.retain(|playhead| playhead.strong_count() > 0);
+        let playhead = playheads
+            .iter()
+            .filter_map(Weak::upgrade)
+            .map(|playhead| playhead.get())
+            .min()
+            .unwrap_or(self.position());
+
+        let mut tempo = self.tempo.borrow_mut();
+        tempo.prune(&playhead);
+        tempo.ramp(position, bpm, length, curve);
+        Some(())
+    }
+
+    // NOTE: Finds where the page and step are played next, counting the upcoming step
+    fn next_position(&self, page: usize, step: usize) -> Option<NoteValue> {
+        if page >= self.pages || step >= self.steps_per_page(page) {
+            return None;
+        }
+
+        let mut position = self.position();
+        let mut current = (self.page, self.step);
+        while current != (page, step) {
+            position = position + self.step_length(current.0, current.1);
+            current.1 = (current.1 + 1) % self.steps_per_page(current.0);
+            if current.1 == 0 {
+                current.0 = (current.0 + 1) % self.pages;
+            }
+        }
+        Some(position)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        arrangement::{Entry, Section},
+        theory::Note,
+        track::{
+            tests::{Events, Recorder},
+            Condition, Step,
+        },
+    };
 
     #[test]
     fn test_tick_quarter() {
@@ -128,6 +785,679 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_tick_sixteenth() {
+        let mut seq = Sequencer::new(60.0, 1, Resolution::Sixteenth, 0.0, 100);
+        assert_eq!(seq.steps_per_page(0), 16);
+        assert_eq!(seq.seconds_per_beat(), 0.25);
+
+        let mut ticks = vec![];
+        seq.tick(0.85, |time, _page, step| {
+            ticks.push((time, step));
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(ticks, vec![(0.1, 0), (0.35, 1), (0.6, 2), (0.85, 3)]);
+    }
+
+    #[test]
+    fn test_tick_thirty_second() {
+        let seq = Sequencer::new(120.0, 1, Resolution::ThirtySecond, 0.0, 100);
+        assert_eq!(seq.steps_per_page(0), 32);
+        assert_eq!(seq.seconds_per_beat(), 0.0625);
+    }
+
+    #[test]
+    fn test_tick_triplet() {
+        let seq = Sequencer::new(60.0, 1, Resolution::EighthTriplet, 0.0, 100);
+        assert_eq!(seq.steps_per_page(0), 12);
+        assert_eq!(seq.seconds_per_beat(), 1.0 / 3.0);
+
+        let mut seq = Sequencer::new(60.0, 1, Resolution::SixteenthTriplet, 0.0, 100);
+        assert_eq!(seq.steps_per_page(0), 24);
+        let mut steps = 0;
+        seq.tick(3.85, |_time, page, _step| {
+            assert_eq!(page, 0);
+            steps += 1;
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(steps, 24);
+    }
+
+    #[test]
+    fn test_with_resolution() {
+        let mut quarter = Sequencer::new(60.0, 2, Resolution::Quarter, 0.0, 100);
+        quarter.set_time_signature("3/4".parse().unwrap());
+        quarter.tick(1.85, |_time, _page, _step| Ok(())).unwrap();
+
+        let mut sixteenth = quarter.with_resolution(Resolution::Sixteenth);
+        assert_eq!(sixteenth.steps_per_page(0), 12);
+
+        let mut quarter_ticks = vec![];
+        quarter
+            .tick(3.85, |time, page, step| {
+                quarter_ticks.push((time, page, step));
+                Ok(())
+            })
+            .unwrap();
+        let mut sixteenth_ticks = vec![];
+        sixteenth
+            .tick(3.85, |time, page, step| {
+                sixteenth_ticks.push((time, page, step));
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(quarter_ticks, vec![(2.1, 0, 2), (3.1, 1, 0)]);
+        assert_eq!(sixteenth_ticks.len(), 8);
+        assert_eq!(sixteenth_ticks[0], (2.1, 0, 8));
+        assert_eq!(sixteenth_ticks[4], (3.1, 1, 0));
+    }
+
+    #[test]
+    fn test_with_resolution_pruned() {
+        let mut quarter = Sequencer::new(60.0, 1, Resolution::Quarter, 0.0, 100);
+        quarter.schedule_bpm(0, 1, 120.0).unwrap();
+        quarter.tick(2.05, |_time, _page, _step| Ok(())).unwrap();
+
+        // NOTE: Folds the first change into the initial tempo, which the sibling no longer starts from
+        quarter.schedule_bpm(0, 2, 90.0).unwrap();
+        assert_eq!(quarter.tempo().initial_bpm(), 120.0);
+        assert_eq!(quarter.tempo().changes().len(), 1);
+
+        let mut eighth = quarter.with_resolution(Resolution::Eighth);
+        let mut quarter_ticks = vec![];
+        quarter
+            .tick(4.0, |time, _page, step| {
+                quarter_ticks.push((time, step));
+                Ok(())
+            })
+            .unwrap();
+        let mut eighth_ticks = vec![];
+        eighth
+            .tick(4.0, |time, _page, step| {
+                eighth_ticks.push((time, step));
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(quarter_ticks, vec![(2.6, 0), (3.1, 1), (3.6, 2)]);
+        assert_eq!(eighth_ticks.len(), 6);
+        assert_eq!(
+            eighth_ticks
+                .into_iter()
+                .filter(|(_time, step)| step % 2 == 0)
+                .map(|(time, step)| (time, step / 2))
+                .collect::<Vec<_>>(),
+            quarter_ticks
+        );
+    }
+
+    #[test]
+    fn test_tick_time_signature() {
+        let mut seq = Sequencer::new(60.0, 2, Resolution::Quarter, 0.0, 100);
+        seq.set_time_signature("3/4".parse().unwrap());
+        seq.set_page_time_signature(1, "7/8".parse().unwrap());
+        assert_eq!(seq.steps_per_page(0), 3);
+        assert_eq!(seq.steps_per_page(1), 4);
+
+        let mut ticks = vec![];
+        seq.tick(6.95, |time, page, step| {
+            ticks.push((time, page, step));
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(
+            ticks,
+            vec![
+                (0.1, 0, 0),
+                (1.1, 0, 1),
+                (2.1, 0, 2),
+                (3.1, 1, 0),
+                (4.1, 1, 1),
+                (5.1, 1, 2),
+                (6.1, 1, 3),
+                // NOTE: The last step of 7/8 is an eighth long
+                (6.6, 0, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tick_swing() {
+        let mut seq = Sequencer::new(60.0, 1, Resolution::Eighth, 0.0, 100);
+        seq.set_groove(Groove::swing(0.75).unwrap());
+
+        let mut ticks = vec![];
+        seq.tick(1.85, |time, _page, step| {
+            ticks.push((time, step));
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(ticks, vec![(0.1, 0), (0.85, 1), (1.1, 2), (1.85, 3)]);
+    }
+
+    #[test]
+    fn test_tick_with_velocity() {
+        let mut seq = Sequencer::new(60.0, 1, Resolution::Quarter, 0.0, 100);
+        seq.set_groove(Groove::new(vec![0.0, -0.1], vec![1.0, 0.5]).unwrap());
+
+        let mut ticks = vec![];
+        seq.tick_with_velocity(2.95, |time, _page, step, velocity| {
+            ticks.push((time, step, velocity));
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(
+            ticks,
+            vec![(0.1, 0, 1.0), (1.0, 1, 0.5), (2.1, 2, 1.0), (3.0, 3, 0.5)]
+        );
+    }
+
+    #[test]
+    fn test_tick_tempo_change() {
+        let mut seq = Sequencer::new(60.0, 1, Resolution::Quarter, 0.0, 100);
+        seq.schedule_bpm(0, 2, 120.0).unwrap();
+        assert!(seq.schedule_bpm(0, 4, 120.0).is_none());
+
+        let mut ticks = vec![];
+        seq.tick(3.85, |time, _page, step| {
+            ticks.push((time, step));
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(
+            ticks,
+            vec![(0.1, 0), (1.1, 1), (2.1, 2), (2.6, 3), (3.1, 0), (3.6, 1)]
+        );
+        assert_eq!(seq.bpm(), 120.0);
+        assert_eq!(seq.seconds_per_beat(), 0.5);
+        assert!(seq.set_bpm(0.0).is_none());
+        assert!(seq.schedule_bpm(0, 0, -60.0).is_none());
+
+        seq.set_bpm(60.0).unwrap();
+        assert_eq!(seq.tempo().changes().len(), 1);
+        let mut ticks = vec![];
+        seq.tick(5.05, |time, _page, step| {
+            ticks.push((time, step));
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(ticks, vec![(4.1, 2), (5.1, 3)]);
+    }
+
+    #[test]
+    fn test_tick_tempo_ramp() {
+        let mut seq = Sequencer::new(60.0, 1, Resolution::Quarter, 0.0, 100);
+        assert!(seq
+            .ramp_bpm(f32::NAN, Duration::Whole.note_value(), Curve::Linear)
+            .is_none());
+        seq.ramp_bpm(120.0, Duration::Whole.note_value(), Curve::Linear)
+            .unwrap();
+        assert_eq!(seq.tempo().changes().len(), 1);
+
+        let mut ticks = vec![];
+        seq.tick(10.0, |time, _page, _step| {
+            ticks.push(time);
+            Ok(())
+        })
+        .unwrap();
+
+        // NOTE: Each beat of the ramp takes ln(b1 / b0) / (b1 - b0) * 60 seconds
+        let beat = |from: f64, to: f64| (to / from).ln() / (to - from) * 60.0;
+        let mut expected = 0.1;
+        for (i, time) in ticks.iter().enumerate().take(5) {
+            assert!((time - expected).abs() < 1e-9, "{i}: {time} != {expected}");
+            let from = 60.0 + 15.0 * i as f64;
+            expected += if i < 4 { beat(from, from + 15.0) } else { 0.5 };
+        }
+        assert!((ticks[5] - ticks[4] - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_with_resolution_tempo() {
+        let mut quarter = Sequencer::new(60.0, 1, Resolution::Quarter, 0.0, 100);
+        quarter.schedule_bpm(0, 1, 120.0).unwrap();
+        quarter.tick(0.45, |_time, _page, _step| Ok(())).unwrap();
+
+        let mut eighth = quarter.with_resolution(Resolution::Eighth);
+        let mut ticks = vec![];
+        eighth
+            .tick(1.95, |time, _page, step| {
+                ticks.push((time, step));
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(
+            ticks,
+            vec![(1.1, 2), (1.35, 3), (1.6, 4), (1.85, 5), (2.1, 6)]
+        );
+
+        // NOTE: The quarter sequencer is a whole note in, where its tempo change reaches the eighths
+        quarter.tick(1.95, |_time, _page, _step| Ok(())).unwrap();
+        quarter.set_bpm(60.0).unwrap();
+        assert_eq!(eighth.tempo().changes().len(), 1);
+        let mut ticks = vec![];
+        eighth
+            .tick(3.95, |time, _page, step| {
+                ticks.push((time, step));
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(
+            ticks,
+            vec![(2.35, 7), (2.6, 0), (3.1, 1), (3.6, 2), (4.1, 3)]
+        );
+    }
+
+    #[test]
+    fn test_tick_tracks() {
+        let events = Events::default();
+        let mut seq = Sequencer::new(60.0, 1, Resolution::Quarter, 0.0, 100);
+        seq.add_track(Track::with_steps(
+            "three",
+            vec![Some(Note::C4.into()), None, None],
+            Recorder::new(&events),
+        ));
+        seq.add_track(Track::with_steps(
+            "four",
+            vec![
+                Some(
+                    Step::new(Note::C2)
+                        .with_velocity(0.5)
+                        .with_length(Duration::Half.note_value()),
+                ),
+                None,
+                None,
+                None,
+            ],
+            Recorder::new(&events),
+        ));
+
+        seq.tick(6.95, |_time, _page, _step| Ok(())).unwrap();
+        assert_eq!(
+            *events.borrow(),
+            vec![
+                (0.1, Note::C4, 1.0, 1.0),
+                (0.1, Note::C2, 0.5, 2.0),
+                (3.1, Note::C4, 1.0, 1.0),
+                (4.1, Note::C2, 0.5, 2.0),
+                (6.1, Note::C4, 1.0, 1.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tick_tracks_mute_and_solo() {
+        let events = Events::default();
+        let mut seq = Sequencer::new(60.0, 1, Resolution::Quarter, 0.0, 100);
+        for (name, note) in [("bd", Note::C2), ("sd", Note::D2), ("hh", Note::Fsharp2)] {
+            seq.add_track(Track::with_steps(
+                name,
+                vec![Some(note.into())],
+                Recorder::new(&events),
+            ));
+        }
+        let notes = |seq: &mut Sequencer, current_time: f64| {
+            events.borrow_mut().clear();
+            seq.tick(current_time, |_time, _page, _step| Ok(()))
+                .unwrap();
+            events
+                .borrow()
+                .iter()
+                .map(|event| event.1.clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            notes(&mut seq, 0.0),
+            vec![Note::C2, Note::D2, Note::Fsharp2]
+        );
+        seq.track_mut("sd").unwrap().set_muted(true);
+        assert_eq!(notes(&mut seq, 1.0), vec![Note::C2, Note::Fsharp2]);
+        seq.track_mut("hh").unwrap().set_soloed(true);
+        assert_eq!(notes(&mut seq, 2.0), vec![Note::Fsharp2]);
+        seq.remove_track("hh").unwrap();
+        assert_eq!(notes(&mut seq, 3.0), vec![Note::C2]);
+    }
+
+    #[test]
+    fn test_tick_tracks_probability() {
+        let render = |seed: u64| {
+            let events = Events::default();
+            let mut seq = Sequencer::new(60.0, 1, Resolution::Sixteenth, 0.0, 100);
+            seq.set_seed(seed);
+            seq.add_track(Track::with_steps(
+                "hh",
+                vec![Some(Step::new(Note::Fsharp2).with_probability(0.5))],
+                Recorder::new(&events),
+            ));
+            seq.add_track(Track::with_steps(
+                "never",
+                vec![Some(Step::new(Note::C2).with_probability(0.0))],
+                Recorder::new(&events),
+            ));
+            seq.tick(15.0, |_time, _page, _step| Ok(())).unwrap();
+            let times = events
+                .borrow()
+                .iter()
+                .map(|event| event.0)
+                .collect::<Vec<_>>();
+            times
+        };
+
+        let times = render(1);
+        assert!(times.len() > 10 && times.len() < 50, "{}", times.len());
+        assert_eq!(times, render(1));
+        assert_ne!(times, render(2));
+    }
+
+    #[test]
+    fn test_tick_tracks_conditions() {
+        let events = Events::default();
+        let mut seq = Sequencer::new(60.0, 1, Resolution::Quarter, 0.0, 100);
+        let step =
+            |note: Note, condition: Condition| Some(Step::new(note).with_condition(condition));
+        seq.add_track(Track::with_steps(
+            "ratio",
+            vec![step(Note::C2, Condition::Ratio(2, 3)), None],
+            Recorder::new(&events),
+        ));
+        seq.add_track(Track::with_steps(
+            "pre",
+            vec![
+                step(Note::D2, Condition::Pre),
+                step(Note::E2, Condition::First),
+            ],
+            Recorder::new(&events),
+        ));
+        seq.add_track(Track::with_steps(
+            "nei",
+            vec![step(Note::F2, Condition::NotNei), None],
+            Recorder::new(&events),
+        ));
+        seq.add_track(Track::with_steps(
+            "fill",
+            vec![None, step(Note::G2, Condition::Fill)],
+            Recorder::new(&events),
+        ));
+        let loops = |seq: &mut Sequencer, current_time: f64| {
+            events.borrow_mut().clear();
+            seq.tick(current_time, |_time, _page, _step| Ok(()))
+                .unwrap();
+            events
+                .borrow()
+                .iter()
+                .map(|event| event.1.clone())
+                .collect::<Vec<_>>()
+        };
+
+        // NOTE: Pre has no previous condition on the first loop, so Nei plays
+        assert_eq!(loops(&mut seq, 1.0), vec![Note::F2, Note::E2]);
+        assert_eq!(loops(&mut seq, 3.0), vec![Note::C2, Note::D2]);
+        seq.track_mut("pre").unwrap().set_muted(true);
+        seq.set_fill(true);
+        assert_eq!(loops(&mut seq, 5.0), vec![Note::F2, Note::G2]);
+    }
+
+    #[test]
+    fn test_tick_arrangement() {
+        let mut seq = Sequencer::new(60.0, 3, Resolution::Quarter, 0.0, 100);
+        seq.set_page_time_signature(2, "2/4".parse().unwrap());
+        let arrangement = Arrangement::new(
+            vec![
+                Section::new("A", vec![0, 1]).unwrap(),
+                Section::new("fill", vec![2]).unwrap(),
+            ],
+            vec![Entry::play("fill", 1), Entry::play("A", 1)],
+        )
+        .unwrap();
+        assert!(seq
+            .set_arrangement(
+                Arrangement::new(
+                    vec![Section::new("A", vec![3]).unwrap()],
+                    vec![Entry::play("A", 1)]
+                )
+                .unwrap()
+            )
+            .is_none());
+        seq.set_arrangement(arrangement).unwrap();
+        assert_eq!(seq.section(), Some("fill"));
+
+        let mut ticks = vec![];
+        seq.tick(9.05, |time, page, step| {
+            ticks.push((time, page, step));
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(ticks.len(), 10);
+        assert!(!seq.is_finished());
+
+        // NOTE: The last step is scheduled at 9.1 and lasts a second
+        seq.tick(10.05, |_time, _page, _step| panic!("should be finished"))
+            .unwrap();
+        assert!(!seq.is_finished());
+        seq.tick(10.1, |_time, _page, _step| panic!("should be finished"))
+            .unwrap();
+        assert!(seq.is_finished());
+        assert_eq!(ticks[0], (0.1, 2, 0));
+        assert_eq!(ticks[2], (2.1, 0, 0));
+        assert_eq!(ticks[6], (6.1, 1, 0));
+        assert_eq!(ticks[9], (9.1, 1, 3));
+
+        let mut sibling = seq.with_resolution(Resolution::Eighth);
+        assert!(sibling.is_finished());
+        sibling
+            .tick(100.0, |_time, _page, _step| panic!("should be finished"))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_transport() {
+        let mut seq = Sequencer::new(60.0, 2, Resolution::Eighth, 10.0, 100);
+        seq.set_page_time_signature(1, "3/4".parse().unwrap());
+        assert_eq!(seq.transport(10.0), None);
+
+        seq.tick(10.0, |_time, _page, _step| Ok(())).unwrap();
+        assert_eq!(seq.transport(10.05), None);
+        let transport = seq.transport(10.1).unwrap();
+        assert_eq!(
+            (transport.bar(), transport.beat(), transport.page()),
+            (0, 0, 0)
+        );
+        assert_eq!(transport.step(), 0);
+        assert_eq!(transport.time(), 10.1);
+        assert_eq!(transport.song_time(), 0.0);
+        assert_eq!(transport.next_time(), 10.6);
+
+        // NOTE: Steps are scheduled ahead, but the transport follows the time heard
+        seq.tick(14.45, |_time, _page, _step| Ok(())).unwrap();
+        let transport = seq.transport(11.7).unwrap();
+        assert_eq!(
+            (transport.bar(), transport.beat(), transport.step()),
+            (0, 1, 3)
+        );
+        assert_eq!(transport.next_time(), 12.1);
+        assert!((transport.song_time() - 1.6).abs() < 1e-9);
+
+        let transport = seq.transport(14.5).unwrap();
+        assert_eq!(
+            (transport.bar(), transport.beat(), transport.page()),
+            (1, 0, 1)
+        );
+        assert_eq!(transport.step(), 0);
+        assert_eq!(transport.next_time(), 14.6);
+    }
+
+    #[test]
+    fn test_tick_midi_sync() {
+        let mut seq = Sequencer::new(60.0, 1, Resolution::Quarter, 0.0, 100);
+        seq.set_midi_sync(true);
+        // NOTE: 120 bpm is a pulse every 1/48 second
+        let pulse = |i: u64| 1.0 + i as f64 / 48.0;
+        let tick = |seq: &mut Sequencer, current_time: f64| {
+            let mut ticks = vec![];
+            seq.tick(current_time, |time, _page, step| {
+                ticks.push((time, step));
+                Ok(())
+            })
+            .unwrap();
+            ticks
+        };
+        let mut ticks = vec![];
+
+        seq.receive_midi(&[0xF8, 0xFA], 0.9);
+        ticks.extend(tick(&mut seq, 0.9));
+        seq.receive_midi(&[0xF8], pulse(0));
+        ticks.extend(tick(&mut seq, 1.0));
+        for i in 1..24 {
+            seq.receive_midi(&[0xF8], pulse(i));
+        }
+        ticks.extend(tick(&mut seq, pulse(23)));
+        for i in 24..30 {
+            seq.receive_midi(&[0xF8], pulse(i));
+        }
+        seq.receive_midi(&[0xFC], pulse(30));
+        ticks.extend(tick(&mut seq, pulse(30)));
+        ticks.extend(tick(&mut seq, 3.0));
+
+        assert!((seq.bpm() - 120.0).abs() < 1e-3);
+        assert_eq!(ticks.len(), 2);
+        assert!((ticks[0].0 - 1.1).abs() < 1e-9);
+        assert!((ticks[1].0 - 1.6).abs() < 1e-9);
+
+        // NOTE: Two pulses 10ms apart after a start are too few to estimate a tempo from
+        seq.receive_midi(&[0xFA, 0xF8], 5.0);
+        seq.receive_midi(&[0xF8], 5.01);
+        ticks.extend(tick(&mut seq, 5.01));
+        assert_eq!(ticks.len(), 2);
+        for i in 2..8 {
+            seq.receive_midi(&[0xF8], 5.0 + i as f64 / 48.0);
+        }
+        ticks.extend(tick(&mut seq, 5.1));
+        assert_eq!(ticks.len(), 3);
+        assert_eq!(ticks[2].1, 0);
+        assert!((seq.bpm() - 120.0).abs() < 5.0, "{}", seq.bpm());
+
+        // NOTE: The tempo map was left as is and takes over again
+        assert_eq!(seq.tempo().changes().len(), 0);
+        seq.set_midi_sync(false);
+        assert_eq!(seq.bpm(), 60.0);
+    }
+
+    fn catch_up(catch_up: CatchUp) -> (Vec<(f64, usize)>, usize, usize) {
+        let mut seq = Sequencer::new(60.0, 1, Resolution::Quarter, 0.0, 100);
+        seq.set_catch_up_policy(catch_up);
+        let mut ticks = vec![];
+        for current_time in [0.0, 3.5, 4.0] {
+            seq.tick(current_time, |time, _page, step| {
+                ticks.push((time, step));
+                Ok(())
+            })
+            .unwrap();
+        }
+        (ticks, seq.late_steps(), seq.dropped_steps())
+    }
+
+    #[test]
+    fn test_catch_up_burst() {
+        assert_eq!(
+            catch_up(CatchUp::Burst),
+            (vec![(0.1, 0), (1.1, 1), (2.1, 2), (3.1, 3), (4.1, 0)], 3, 0)
+        );
+    }
+
+    #[test]
+    fn test_catch_up_skip() {
+        assert_eq!(catch_up(CatchUp::Skip), (vec![(0.1, 0), (4.1, 0)], 0, 3));
+    }
+
+    #[test]
+    fn test_catch_up_reanchor() {
+        // NOTE: The first missed step plays as if started now and the next one follows at 4.6
+        assert_eq!(
+            catch_up(CatchUp::Reanchor),
+            (vec![(0.1, 0), (3.6, 1)], 1, 0)
+        );
+
+        let mut seq = Sequencer::new(60.0, 1, Resolution::Quarter, 0.0, 100);
+        seq.set_catch_up_policy(CatchUp::Reanchor);
+        seq.tick(0.0, |_time, _page, _step| Ok(())).unwrap();
+        seq.tick(3.5, |_time, _page, _step| Ok(())).unwrap();
+        // NOTE: A second into the song when the reanchored step is heard
+        let transport = seq.transport(3.6).unwrap();
+        assert_eq!(transport.step(), 1);
+        assert!((transport.song_time() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_catch_up_compress() {
+        // NOTE: Missed steps play at 4x speed from now on, and the step due at 4.1 waits behind them
+        assert_eq!(
+            catch_up(CatchUp::Compress),
+            (
+                vec![(0.1, 0), (3.6, 1), (3.85, 2), (4.1, 3), (4.35, 0)],
+                4,
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn test_catch_up_compress_throttled() {
+        let mut seq = Sequencer::new(120.0, 1, Resolution::Sixteenth, 0.0, 100);
+        seq.set_catch_up_policy(CatchUp::Compress);
+
+        // NOTE: A background tab ticks once a second, then the tab is back to a tick every 25 ms
+        let current_times = (0..5)
+            .map(|i| i as f64)
+            .chain((0..200).map(|i| 5.0 + i as f64 * 0.025));
+        let mut times = vec![];
+        for current_time in current_times {
+            seq.tick(current_time, |time, _page, _step| {
+                times.push(time);
+                Ok(())
+            })
+            .unwrap();
+        }
+
+        assert!(times.windows(2).all(|pair| pair[0] <= pair[1]), "{times:?}");
+        assert!(seq.late_steps() > 0);
+        assert_eq!(seq.catch_up_time, None);
+        let last = &times[times.len() - 2..];
+        assert!((last[1] - last[0] - 0.125).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_accents() {
+        let mut seq = Sequencer::new(60.0, 2, Resolution::Eighth, 0.0, 100);
+        seq.set_page_time_signature(1, "6/8".parse().unwrap());
+        assert_eq!(
+            seq.accents(),
+            vec![
+                vec![
+                    Accent::Strong,
+                    Accent::Offbeat,
+                    Accent::Weak,
+                    Accent::Offbeat,
+                    Accent::Medium,
+                    Accent::Offbeat,
+                    Accent::Weak,
+                    Accent::Offbeat,
+                ],
+                vec![
+                    Accent::Strong,
+                    Accent::Weak,
+                    Accent::Weak,
+                    Accent::Medium,
+                    Accent::Weak,
+                    Accent::Weak,
+                ],
+            ]
+        );
+    }
+
     #[test]
     fn test_seconds_per_beat_60_4() {
         let seq = Sequencer::new(60.0, 1, Resolution::Quarter, 0.0, 100);
@@ -1,6 +1,6 @@
 use wasm_bindgen::prelude::*;
 
-use crate::result::Result;
+use crate::{result::Result, sequencer::Sequencer};
 
 pub mod forest;
 pub mod metronome;
@@ -28,8 +28,29 @@ impl Song {
     pub fn tick(&mut self) -> Result<()> {
         self.inner.tick()
     }
+
+    #[inline]
+    pub fn sequencer(&mut self) -> Option<&mut Sequencer> {
+        self.inner.sequencer()
+    }
+
+    #[inline]
+    pub fn is_finished(&mut self) -> bool {
+        self.inner.is_finished()
+    }
 }
 
 pub trait Playable {
     fn tick(&mut self) -> Result<()>;
+
+    // NOTE: Songs driven by a sequencer expose it for tempo control from the player
+    fn sequencer(&mut self) -> Option<&mut Sequencer> {
+        None
+    }
+
+    // NOTE: Songs end when their arrangement does, songs without one never end
+    fn is_finished(&mut self) -> bool {
+        self.sequencer()
+            .is_some_and(|sequencer| sequencer.is_finished())
+    }
 }
@@ -7,6 +7,8 @@ use web_sys::AudioContext;
 
 use crate::{
     arps::UpDownArpeggiator,
+    clock::Clock,
+    instrument::Instrument,
     result::Result,
     sampler::MelodicSampler,
     sequencer::{Resolution, Sequencer},
@@ -17,8 +19,8 @@ use super::{Playable, Song};
 
 #[wasm_bindgen]
 pub struct Forest {
-    ctx: AudioContext,
-    sampler: MelodicSampler,
+    clock: Rc<dyn Clock>,
+    instrument: Box<dyn Instrument>,
     sequencer: Sequencer,
     rng: Rc<RefCell<ChaCha8Rng>>,
     lhs_chords: Vec<Vec<Note>>,
@@ -27,58 +29,77 @@ pub struct Forest {
 
 #[wasm_bindgen]
 impl Forest {
-    #[wasm_bindgen(constructor)]
-    pub fn new(ctx: AudioContext, seed: u64) -> Forest {
-        let sequencer = Sequencer::new(74.0, 8, Resolution::Eighth, ctx.current_time(), 100);
-        let rng = Rc::new(RefCell::new(ChaCha8Rng::seed_from_u64(seed)));
-
-        let beats_per_measure = sequencer.resolution().duration().beats_per_measure();
-        let lhs_chords = vec![
-            UpDownArpeggiator::new(Chord::Major9th(Note::G1).notes())
-                .take(beats_per_measure)
-                .collect(),
-            UpDownArpeggiator::new(Chord::Major9th(Note::C1).notes())
-                .take(beats_per_measure)
-                .collect(),
-        ];
-        let rhs_chords = vec![
-            Chord::Major9th(Note::G3).notes(),
-            Chord::Major9th(Note::C3).notes(),
-        ];
-
-        Self {
-            ctx: ctx.clone(),
-            sampler: MelodicSampler::new(ctx),
-            sequencer,
-            rng,
-            lhs_chords,
-            rhs_chords,
-        }
+    // NOTE: Resolves once the samples are loaded into the sampler the song plays
+    #[wasm_bindgen]
+    pub async fn load(ctx: AudioContext, seed: u64) -> Result<Forest> {
+        Self::with_tuning(ctx, seed, Tuning::default()).await
     }
 
     #[wasm_bindgen]
-    pub async fn init(&mut self) -> Result<()> {
-        self.sampler
+    pub fn into_song(self) -> Song {
+        self.into()
+    }
+}
+
+impl Forest {
+    pub async fn with_tuning(ctx: AudioContext, seed: u64, tuning: Tuning) -> Result<Forest> {
+        let mut sampler = MelodicSampler::with_tuning(ctx.clone(), tuning);
+        sampler
             .insert(Note::A0, include_bytes!("../../samples/a0.m4a"))
             .await?;
-        self.sampler
+        sampler
             .insert(Note::A1, include_bytes!("../../samples/a1.m4a"))
             .await?;
-        self.sampler
+        sampler
             .insert(Note::A2, include_bytes!("../../samples/a2.m4a"))
             .await?;
-        self.sampler
+        sampler
             .insert(Note::A3, include_bytes!("../../samples/a3.m4a"))
             .await?;
-        self.sampler
+        sampler
             .insert(Note::A4, include_bytes!("../../samples/a4.m4a"))
             .await?;
-        Ok(())
+        Ok(Self::with_instrument(Rc::new(ctx), seed, Box::new(sampler)))
     }
 
-    #[wasm_bindgen]
-    pub fn into_song(self) -> Song {
-        self.into()
+    pub fn with_instrument(
+        clock: Rc<dyn Clock>,
+        seed: u64,
+        instrument: Box<dyn Instrument>,
+    ) -> Forest {
+        let sequencer = Sequencer::new(74.0, 8, Resolution::Eighth, clock.current_time(), 100);
+        let rng = Rc::new(RefCell::new(ChaCha8Rng::seed_from_u64(seed)));
+
+        let steps_per_page = sequencer.steps_per_page(0);
+        let key = Key::new(PitchClass::C, Scale::Lydian);
+        let progression = "Vmaj9 Imaj9";
+        let lhs_chords = key
+            .progression(progression, 1)
+            .expect("should be parsed progression")
+            .iter()
+            .map(|chord| {
+                UpDownArpeggiator::new(chord.notes())
+                    .take(steps_per_page)
+                    .collect()
+            })
+            .collect();
+        let rhs_chords = VoiceLeader::new(Note::C3, Note::A4)
+            .lead(
+                &key.progression(progression, 3)
+                    .expect("should be parsed progression"),
+            )
+            .iter()
+            .map(|voicing| voicing.notes())
+            .collect();
+
+        Self {
+            clock,
+            instrument,
+            sequencer,
+            rng,
+            lhs_chords,
+            rhs_chords,
+        }
     }
 }
 
@@ -90,14 +111,14 @@ impl From<Forest> for Song {
 
 impl Playable for Forest {
     fn tick(&mut self) -> Result<()> {
-        let ctx = self.ctx.clone();
-        let sampler = self.sampler.clone();
+        let instrument = &self.instrument;
+        let duration = self.sequencer.seconds_per_beat();
         let rng_ref = self.rng.clone();
         let lhs_chords = self.lhs_chords.clone();
         let rhs_chords = self.rhs_chords.clone();
 
         self.sequencer
-            .tick(ctx.current_time(), move |time, page, step| {
+            .tick(self.clock.current_time(), move |time, page, step| {
                 let chord_index = if page >= 4 { 1 } else { 0 };
 
                 // left hand
@@ -106,9 +127,7 @@ impl Playable for Forest {
                         .get(chord_index)
                         .expect("should be got chord from chords");
                     let note = chord.get(step).expect("should be got note from chord");
-                    let src = sampler.buffer_node(note)?;
-                    src.connect_with_audio_node(&ctx.destination())?;
-                    src.start_with_when(time)?;
+                    instrument.play(time, note, 1.0, duration)?;
                 }
 
                 // right hand
@@ -125,12 +144,62 @@ impl Playable for Forest {
                     let note = chord
                         .get(note_index)
                         .expect("should be got note from chord");
-                    let src = sampler.buffer_node(note)?;
-                    src.connect_with_audio_node(&ctx.destination())?;
-                    src.start_with_when(time)?;
+                    instrument.play(time, note, 1.0, duration)?;
                 }
 
                 Ok(())
             })
     }
+
+    #[inline]
+    fn sequencer(&mut self) -> Option<&mut Sequencer> {
+        Some(&mut self.sequencer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        clock::ManualClock,
+        track::tests::{Events, Recorder},
+    };
+
+    fn record(seed: u64) -> Events {
+        let clock = ManualClock::new(0.0);
+        let events = Events::default();
+        let mut forest =
+            Forest::with_instrument(Rc::new(clock.clone()), seed, Recorder::new(&events));
+        for _ in 0..32 {
+            forest.tick().unwrap();
+            clock.advance(0.1);
+        }
+        events
+    }
+
+    #[test]
+    fn test_tick() {
+        let events = record(0);
+        let events = events.borrow();
+        let first = events
+            .iter()
+            .filter(|event| event.0 == 0.1)
+            .collect::<Vec<_>>();
+        assert_eq!(first.len(), 2);
+        assert_eq!(first[0].1, Note::G1);
+
+        // NOTE: The left hand arpeggiates every eighth of the first page, which lasts 3.24 seconds
+        let step = 60.0 / 74.0 / 2.0;
+        let lhs = events
+            .iter()
+            .filter(|event| event.1 < Note::C3)
+            .map(|event| event.0)
+            .collect::<Vec<_>>();
+        assert_eq!(lhs.len(), 8);
+        for (i, time) in lhs.iter().enumerate() {
+            assert!((time - 0.1 - step * i as f64).abs() < 1e-9);
+        }
+
+        assert_eq!(*events, *record(0).borrow());
+    }
 }
@@ -1,49 +1,94 @@
+use std::rc::Rc;
+
 use wasm_bindgen::prelude::*;
 use web_sys::AudioContext;
 
 use crate::{
+    clock::Clock,
+    instrument::Instrument,
     result::Result,
     sampler::MelodicSampler,
     sequencer::{Resolution, Sequencer},
-    theory::Note,
+    theory::{Accent, Note, TimeSignature},
 };
 
 use super::{Playable, Song};
 
 #[wasm_bindgen]
 pub struct Metronome {
-    ctx: AudioContext,
-    sampler: MelodicSampler,
+    clock: Rc<dyn Clock>,
+    instrument: Box<dyn Instrument>,
     sequencer: Sequencer,
 }
 
 #[wasm_bindgen]
 impl Metronome {
-    #[wasm_bindgen(constructor)]
-    pub fn new(ctx: AudioContext, bpm: f32) -> Metronome {
-        let sequencer = Sequencer::new(bpm, 1, Resolution::Quarter, ctx.current_time(), 100);
+    // NOTE: Resolves once the samples are loaded into the sampler the metronome plays
+    #[wasm_bindgen]
+    pub async fn load(ctx: AudioContext, bpm: f32) -> Result<Metronome> {
+        Self::with_time_signature(ctx, bpm, TimeSignature::default()).await
+    }
 
-        Self {
-            ctx: ctx.clone(),
-            sampler: MelodicSampler::new(ctx),
-            sequencer,
-        }
+    #[wasm_bindgen]
+    pub fn set_time_signature(&mut self, time_signature: &str) -> Result<()> {
+        let time_signature: TimeSignature = time_signature.parse()?;
+        self.sequencer = Self::sequencer(
+            self.sequencer.bpm(),
+            time_signature,
+            self.clock.current_time(),
+        );
+        Ok(())
     }
 
     #[wasm_bindgen]
-    pub async fn init(&mut self) -> Result<()> {
-        self.sampler
+    pub fn into_song(self) -> Song {
+        self.into()
+    }
+}
+
+impl Metronome {
+    pub async fn with_time_signature(
+        ctx: AudioContext,
+        bpm: f32,
+        time_signature: TimeSignature,
+    ) -> Result<Metronome> {
+        let mut sampler = MelodicSampler::new(ctx.clone());
+        sampler
             .insert(Note::A2, include_bytes!("../../samples/a2.m4a"))
             .await?;
-        self.sampler
+        sampler
             .insert(Note::A3, include_bytes!("../../samples/a3.m4a"))
             .await?;
-        Ok(())
+        Ok(Self::with_instrument(
+            Rc::new(ctx),
+            bpm,
+            time_signature,
+            Box::new(sampler),
+        ))
     }
 
-    #[wasm_bindgen]
-    pub fn into_song(self) -> Song {
-        self.into()
+    pub fn with_instrument(
+        clock: Rc<dyn Clock>,
+        bpm: f32,
+        time_signature: TimeSignature,
+        instrument: Box<dyn Instrument>,
+    ) -> Metronome {
+        let sequencer = Self::sequencer(bpm, time_signature, clock.current_time());
+
+        Self {
+            clock,
+            instrument,
+            sequencer,
+        }
+    }
+
+    // NOTE: Clicks on every unit of the signature, e.g. eighths in 6/8 and 7/8
+    fn sequencer(bpm: f32, time_signature: TimeSignature, current_time: f64) -> Sequencer {
+        let resolution =
+            Resolution::from_duration(time_signature.unit()).unwrap_or(Resolution::Quarter);
+        let mut sequencer = Sequencer::new(bpm, 1, resolution, current_time, 100);
+        sequencer.set_time_signature(time_signature);
+        sequencer
     }
 }
 
@@ -55,21 +100,89 @@ impl From<Metronome> for Song {
 
 impl Playable for Metronome {
     fn tick(&mut self) -> Result<()> {
-        let ctx = self.ctx.clone();
-        let sampler = self.sampler.clone();
+        let instrument = &self.instrument;
+        let duration = self.sequencer.seconds_per_beat();
+        let accents = self.sequencer.accents();
 
         self.sequencer
-            .tick(self.ctx.current_time(), move |time, _page, step| {
-                let src = if step == 0 {
-                    sampler.buffer_node(&Note::C4)?
-                } else {
-                    sampler.buffer_node(&Note::C3)?
+            .tick(self.clock.current_time(), move |time, page, step| {
+                let note = match accents[page][step] {
+                    Accent::Strong => Note::C4,
+                    Accent::Medium => Note::G3,
+                    Accent::Weak => Note::C3,
+                    Accent::Offbeat => return Ok(()),
                 };
+                instrument.play(time, &note, 1.0, duration)
+            })
+    }
 
-                src.connect_with_audio_node(&ctx.destination())?;
-                src.start_with_when(time)?;
+    #[inline]
+    fn sequencer(&mut self) -> Option<&mut Sequencer> {
+        Some(&mut self.sequencer)
+    }
+}
 
-                Ok(())
-            })
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        clock::ManualClock,
+        track::tests::{Events, Recorder},
+    };
+
+    #[test]
+    fn test_tick() {
+        let clock = ManualClock::new(0.0);
+        let events = Events::default();
+        let mut metronome = Metronome::with_instrument(
+            Rc::new(clock.clone()),
+            120.0,
+            "6/8".parse().unwrap(),
+            Recorder::new(&events),
+        );
+
+        for _ in 0..7 {
+            metronome.tick().unwrap();
+            clock.advance(0.25);
+        }
+        let notes = events
+            .borrow()
+            .iter()
+            .map(|event| (event.0, event.1.clone()))
+            .collect::<Vec<_>>();
+        // NOTE: Eighths at 120 bpm, accenting the two dotted quarters of 6/8
+        assert_eq!(
+            notes,
+            vec![
+                (0.1, Note::C4),
+                (0.35, Note::C3),
+                (0.6, Note::C3),
+                (0.85, Note::G3),
+                (1.1, Note::C3),
+                (1.35, Note::C3),
+                (1.6, Note::C4),
+            ]
+        );
+
+        events.borrow_mut().clear();
+        metronome.set_time_signature("3/4").unwrap();
+        for _ in 0..8 {
+            metronome.tick().unwrap();
+            clock.advance(0.25);
+        }
+        let notes = events
+            .borrow()
+            .iter()
+            .map(|event| (event.0, event.1.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            notes,
+            vec![
+                (1.85, Note::C4),
+                (2.35, Note::C3),
+                (2.85, Note::C3),
+                (3.35, Note::C4)
+            ]
+        );
     }
 }
@@ -1,18 +1,23 @@
+use std::rc::Rc;
+
 use wasm_bindgen::prelude::*;
 use web_sys::AudioContext;
 
 use crate::{
+    clock::Clock,
+    groove::Groove,
+    instrument::Instrument,
     machines,
     result::Result,
     sequencer::{Resolution, Sequencer},
+    track::Track,
 };
 
 use super::{Playable, Song};
 
 #[wasm_bindgen]
 pub struct Toy808 {
-    ctx: AudioContext,
-    machine: machines::Toy808,
+    clock: Rc<dyn Clock>,
     sequencer: Sequencer,
 }
 
@@ -21,13 +26,49 @@ impl Toy808 {
     #[wasm_bindgen(constructor)]
     pub fn new(ctx: AudioContext, bpm: f32) -> Result<Toy808> {
         let machine = machines::Toy808::new(ctx.clone());
-        let sequencer = Sequencer::new(bpm, 1, Resolution::Quarter, ctx.current_time(), 100);
+        Ok(Self::with_instruments(
+            Rc::new(ctx),
+            bpm,
+            Box::new(machine.clone()),
+            Box::new(machine),
+        ))
+    }
+
+    #[wasm_bindgen]
+    pub fn set_muted(&mut self, track: &str, muted: bool) -> Result<()> {
+        self.track_mut(track)?.set_muted(muted);
+        Ok(())
+    }
+
+    #[wasm_bindgen]
+    pub fn set_fill(&mut self, fill: bool) {
+        self.sequencer.set_fill(fill);
+    }
 
-        Ok(Self {
-            ctx,
-            machine,
-            sequencer,
-        })
+    #[wasm_bindgen]
+    pub fn set_soloed(&mut self, track: &str, soloed: bool) -> Result<()> {
+        self.track_mut(track)?.set_soloed(soloed);
+        Ok(())
+    }
+
+    #[wasm_bindgen]
+    pub fn set_swing(&mut self, amount: f32) -> Result<()> {
+        let groove = Groove::swing(amount).ok_or_else(|| {
+            JsValue::from_str(&format!(
+                "swing should be between {} and {}",
+                Groove::MIN_SWING,
+                Groove::MAX_SWING
+            ))
+        })?;
+        self.sequencer.set_groove(groove);
+        Ok(())
+    }
+
+    // NOTE: Takes a groove template file, one `offset [velocity]` line per step
+    #[wasm_bindgen]
+    pub fn set_groove(&mut self, data: &[u8]) -> Result<()> {
+        self.sequencer.set_groove(Groove::from_bytes(data)?);
+        Ok(())
     }
 
     #[wasm_bindgen]
@@ -36,6 +77,35 @@ impl Toy808 {
     }
 }
 
+impl Toy808 {
+    pub fn with_instruments(
+        clock: Rc<dyn Clock>,
+        bpm: f32,
+        bd: Box<dyn Instrument>,
+        sd: Box<dyn Instrument>,
+    ) -> Toy808 {
+        let mut sequencer = Sequencer::new(bpm, 1, Resolution::Quarter, clock.current_time(), 100);
+        sequencer.add_track(Track::with_steps(
+            "bd",
+            vec![Some(machines::Toy808::BD.into()), None],
+            bd,
+        ));
+        sequencer.add_track(Track::with_steps(
+            "sd",
+            vec![None, Some(machines::Toy808::SD.into())],
+            sd,
+        ));
+
+        Self { clock, sequencer }
+    }
+
+    fn track_mut(&mut self, name: &str) -> Result<&mut Track> {
+        self.sequencer
+            .track_mut(name)
+            .ok_or_else(|| JsValue::from_str(&format!("track not found: {name}")))
+    }
+}
+
 impl From<Toy808> for Song {
     fn from(value: Toy808) -> Self {
         Song::new("toy808", Box::new(value))
@@ -43,21 +113,64 @@ impl From<Toy808> for Song {
 }
 
 impl Playable for Toy808 {
+    #[inline]
     fn tick(&mut self) -> Result<()> {
-        let ctx = self.ctx.clone();
-        let machine = self.machine.clone();
-
         self.sequencer
-            .tick(self.ctx.current_time(), move |time, _page, step| {
-                if step % 2 == 0 {
-                    let bd = machine.bd(time)?;
-                    bd.connect_with_audio_node(&ctx.destination())?;
-                } else {
-                    let sd = machine.sd(time)?;
-                    sd.connect_with_audio_node(&ctx.destination())?;
-                }
-
-                Ok(())
-            })
+            .tick(self.clock.current_time(), |_time, _page, _step| Ok(()))
+    }
+
+    #[inline]
+    fn sequencer(&mut self) -> Option<&mut Sequencer> {
+        Some(&mut self.sequencer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        clock::ManualClock,
+        theory::Note,
+        track::tests::{Events, Recorder},
+    };
+
+    #[test]
+    fn test_tick() {
+        let clock = ManualClock::new(1.0);
+        let events = Events::default();
+        let mut toy808 = Toy808::with_instruments(
+            Rc::new(clock.clone()),
+            120.0,
+            Recorder::new(&events),
+            Recorder::new(&events),
+        );
+
+        for i in 0..8 {
+            clock.set(1.0 + i as f64 * 0.25);
+            toy808.tick().unwrap();
+        }
+        let notes = events
+            .borrow()
+            .iter()
+            .map(|event| (event.0, event.1.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            notes,
+            vec![
+                (1.1, Note::C2),
+                (1.6, Note::D2),
+                (2.1, Note::C2),
+                (2.6, Note::D2),
+            ]
+        );
+
+        events.borrow_mut().clear();
+        toy808.sequencer.track_mut("sd").unwrap().set_muted(true);
+        clock.set(4.0);
+        toy808.tick().unwrap();
+        assert_eq!(
+            *events.borrow(),
+            vec![(3.1, Note::C2, 1.0, 0.5), (4.1, Note::C2, 1.0, 0.5)]
+        );
     }
 }
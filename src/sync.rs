@@ -0,0 +1,267 @@
+use std::collections::VecDeque;
+
+use crate::theory::NoteValue;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MidiMessage {
+    Clock,
+    Start,
+    Continue,
+    Stop,
+}
+
+impl MidiMessage {
+    // NOTE: Real-time messages are single bytes and may be interleaved with any other message
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0xF8 => Some(MidiMessage::Clock),
+            0xFA => Some(MidiMessage::Start),
+            0xFB => Some(MidiMessage::Continue),
+            0xFC => Some(MidiMessage::Stop),
+            _ => None,
+        }
+    }
+}
+
+// NOTE: Timestamps are in seconds on the AudioContext timeline, and tempo and phase are estimated
+//       by a least squares fit over the last quarter note of pulses to smooth out jitter
+#[derive(Debug, Clone, Default)]
+pub struct MidiClock {
+    running: bool,
+    pulses: u64,
+    window: VecDeque<(u64, f64)>,
+}
+
+impl MidiClock {
+    pub const PPQN: u64 = 24;
+    const WINDOW: usize = Self::PPQN as usize;
+    // NOTE: A sixteenth note of pulses, fewer are dominated by jitter right after a start
+    const MIN_WINDOW: usize = Self::PPQN as usize / 4;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn feed(&mut self, bytes: &[u8], timestamp: f64) -> Vec<MidiMessage> {
+        let messages: Vec<_> = bytes
+            .iter()
+            .filter_map(|byte| MidiMessage::from_byte(*byte))
+            .collect();
+        for message in &messages {
+            self.receive(message, timestamp);
+        }
+        messages
+    }
+
+    pub fn receive(&mut self, message: &MidiMessage, timestamp: f64) {
+        match message {
+            MidiMessage::Clock => {
+                if !self.running {
+                    return;
+                }
+                self.window.push_back((self.pulses, timestamp));
+                if self.window.len() > Self::WINDOW {
+                    self.window.pop_front();
+                }
+                self.pulses += 1;
+            }
+            MidiMessage::Start => {
+                self.running = true;
+                self.pulses = 0;
+                self.window.clear();
+            }
+            MidiMessage::Continue => {
+                self.running = true;
+                self.window.clear();
+            }
+            MidiMessage::Stop => self.running = false,
+        }
+    }
+
+    #[inline]
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    #[inline]
+    pub fn pulses(&self) -> u64 {
+        self.pulses
+    }
+
+    // NOTE: The position of the next pulse, counted in whole notes from the start
+    #[inline]
+    pub fn position(&self) -> NoteValue {
+        NoteValue::new(self.pulses, Self::PPQN * 4).expect("should have non-zero denominator")
+    }
+
+    // NOTE: Returns the slope (seconds per pulse) and intercept of the fit
+    fn fit(&self) -> Option<(f64, f64)> {
+        if self.window.len() < Self::MIN_WINDOW {
+            return None;
+        }
+
+        let n = self.window.len() as f64;
+        let mean_pulse = self
+            .window
+            .iter()
+            .map(|(pulse, _)| *pulse as f64)
+            .sum::<f64>()
+            / n;
+        let mean_time = self.window.iter().map(|(_, time)| time).sum::<f64>() / n;
+        let (covariance, variance) =
+            self.window
+                .iter()
+                .fold((0.0, 0.0), |(covariance, variance), (pulse, time)| {
+                    let pulse = *pulse as f64 - mean_pulse;
+                    (
+                        covariance + pulse * (time - mean_time),
+                        variance + pulse * pulse,
+                    )
+                });
+        let slope = covariance / variance;
+        if slope <= 0.0 {
+            return None;
+        }
+        Some((slope, mean_time - slope * mean_pulse))
+    }
+
+    pub fn bpm(&self) -> Option<f32> {
+        let (slope, _) = self.fit()?;
+        Some((60.0 / (slope * Self::PPQN as f64)) as f32)
+    }
+
+    // NOTE: Extrapolates when the pulse at the position (in whole notes) happens, past or future
+    pub fn time_at(&self, position: &NoteValue) -> Option<f64> {
+        let (slope, intercept) = self.fit()?;
+        let pulse = position.as_f64() * (Self::PPQN * 4) as f64;
+        Some(intercept + slope * pulse)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TapTempo {
+    taps: VecDeque<f64>,
+}
+
+impl TapTempo {
+    const MAX_TAPS: usize = 8;
+    // NOTE: Slower than 30 bpm starts a new measurement
+    const TIMEOUT: f64 = 2.0;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn tap(&mut self, timestamp: f64) -> Option<f32> {
+        if self
+            .taps
+            .back()
+            .is_some_and(|last| timestamp - last > Self::TIMEOUT || timestamp <= *last)
+        {
+            self.taps.clear();
+        }
+        self.taps.push_back(timestamp);
+        if self.taps.len() > Self::MAX_TAPS {
+            self.taps.pop_front();
+        }
+        self.bpm()
+    }
+
+    pub fn bpm(&self) -> Option<f32> {
+        if self.taps.len() < 2 {
+            return None;
+        }
+        let interval = (self.taps.back()? - self.taps.front()?) / (self.taps.len() - 1) as f64;
+        Some((60.0 / interval) as f32)
+    }
+
+    #[inline]
+    pub fn reset(&mut self) {
+        self.taps.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theory::Duration;
+
+    // NOTE: A deterministic jitter of up to +-2ms
+    fn jitter(i: u64) -> f64 {
+        ((i * 7919) % 5) as f64 * 0.001 - 0.002
+    }
+
+    fn pulses(clock: &mut MidiClock, from: u64, to: u64, start: f64, bpm: f64) {
+        let interval = 60.0 / bpm / MidiClock::PPQN as f64;
+        for i in from..to {
+            clock.feed(&[0xF8], start + i as f64 * interval + jitter(i));
+        }
+    }
+
+    #[test]
+    fn test_feed() {
+        let mut clock = MidiClock::new();
+        assert_eq!(
+            clock.feed(&[0xFA, 0x90, 0x3C, 0x7F, 0xF8], 1.0),
+            vec![MidiMessage::Start, MidiMessage::Clock]
+        );
+        assert!(clock.is_running());
+        assert_eq!(clock.pulses(), 1);
+
+        clock.feed(&[0xFC], 1.1);
+        clock.feed(&[0xF8, 0xF8], 1.2);
+        assert!(!clock.is_running());
+        assert_eq!(clock.pulses(), 1);
+
+        clock.feed(&[0xFB, 0xF8], 1.3);
+        assert_eq!(clock.pulses(), 2);
+        clock.feed(&[0xFA], 1.4);
+        assert_eq!(clock.pulses(), 0);
+    }
+
+    #[test]
+    fn test_bpm() {
+        let mut clock = MidiClock::new();
+        clock.feed(&[0xFA], 0.0);
+        assert_eq!(clock.bpm(), None);
+        pulses(&mut clock, 0, 5, 1.0, 120.0);
+        assert_eq!(clock.bpm(), None);
+
+        pulses(&mut clock, 5, 96, 1.0, 120.0);
+        assert!((clock.bpm().unwrap() - 120.0).abs() < 0.5);
+        assert_eq!(clock.position(), Duration::Whole.note_value());
+
+        // NOTE: The fit follows a tempo change within a quarter note
+        pulses(&mut clock, 96, 144, 3.0 - 96.0 / 60.0, 150.0);
+        assert!((clock.bpm().unwrap() - 150.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_time_at() {
+        let mut clock = MidiClock::new();
+        clock.feed(&[0xFA], 0.0);
+        pulses(&mut clock, 0, 48, 1.0, 120.0);
+
+        let time = clock.time_at(&Duration::Half.note_value()).unwrap();
+        assert!((time - 2.0).abs() < 0.005, "{time}");
+        let time = clock.time_at(&Duration::Whole.note_value()).unwrap();
+        assert!((time - 3.0).abs() < 0.005, "{time}");
+    }
+
+    #[test]
+    fn test_tap_tempo() {
+        let mut tap = TapTempo::new();
+        assert_eq!(tap.tap(1.0), None);
+        assert_eq!(tap.tap(1.5), Some(120.0));
+        assert_eq!(tap.tap(2.0), Some(120.0));
+        assert!((tap.tap(2.6).unwrap() - 112.5).abs() < 1e-3);
+
+        // NOTE: A long pause starts over
+        assert_eq!(tap.tap(10.0), None);
+        assert_eq!(tap.tap(11.0), Some(60.0));
+        for i in 2..20 {
+            tap.tap(10.0 + i as f64);
+        }
+        assert_eq!(tap.bpm(), Some(60.0));
+    }
+}
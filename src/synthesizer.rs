@@ -2,8 +2,9 @@ use web_sys::{AudioContext, GainNode, OscillatorType};
 
 use crate::{
     envs::{AmpEnvelope, PitchEnvelope},
+    instrument::{self, Instrument},
     result::Result,
-    theory::Note,
+    theory::{Note, Tuning},
 };
 
 #[derive(Debug, Clone)]
@@ -12,19 +13,44 @@ pub struct Synthesizer {
     ctx: AudioContext,
     shape: OscillatorType,
     amp: AmpEnvelope,
+    tuning: Tuning,
 }
 
 #[allow(dead_code)]
 impl Synthesizer {
     pub fn new(ctx: AudioContext, shape: OscillatorType, amp: AmpEnvelope) -> Self {
-        Self { ctx, shape, amp }
+        Self::with_tuning(ctx, shape, amp, Tuning::default())
+    }
+
+    pub fn with_tuning(
+        ctx: AudioContext,
+        shape: OscillatorType,
+        amp: AmpEnvelope,
+        tuning: Tuning,
+    ) -> Self {
+        Self {
+            ctx,
+            shape,
+            amp,
+            tuning,
+        }
+    }
+
+    #[inline]
+    pub fn tuning(&self) -> &Tuning {
+        &self.tuning
+    }
+
+    #[inline]
+    pub fn set_tuning(&mut self, tuning: Tuning) {
+        self.tuning = tuning;
     }
 
     pub fn node_with_note(&self, note: &Note, time: f64, duration: f64) -> Result<GainNode> {
         let osc = self.ctx.create_oscillator()?;
         osc.set_type(self.shape);
 
-        osc.frequency().set_value(note.freq().into());
+        osc.frequency().set_value(self.tuning.freq(note).into());
 
         osc.start_with_when(time)?;
         osc.stop_with_when(time + duration + self.amp.release())?;
@@ -51,3 +77,10 @@ impl Synthesizer {
         Ok(gain)
     }
 }
+
+impl Instrument for Synthesizer {
+    fn play(&self, time: f64, note: &Note, velocity: f32, duration: f64) -> Result<()> {
+        let gain = self.node_with_note(note, time, duration)?;
+        instrument::connect_with_velocity(&self.ctx, &gain, velocity)
+    }
+}
@@ -0,0 +1,359 @@
+use wasm_bindgen::prelude::*;
+
+use crate::theory::NoteValue;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[wasm_bindgen]
+pub enum Curve {
+    Instant,
+    Linear,
+    Exponential,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TempoChange {
+    position: NoteValue,
+    bpm: f32,
+    curve: Curve,
+    length: NoteValue,
+}
+
+impl TempoChange {
+    #[inline]
+    pub fn position(&self) -> &NoteValue {
+        &self.position
+    }
+
+    #[inline]
+    pub fn bpm(&self) -> f32 {
+        self.bpm
+    }
+
+    #[inline]
+    pub fn curve(&self) -> &Curve {
+        &self.curve
+    }
+
+    #[inline]
+    pub fn length(&self) -> &NoteValue {
+        &self.length
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Segment {
+    start: f64,
+    end: f64,
+    from: f32,
+    to: f32,
+    curve: Curve,
+    length: f64,
+}
+
+impl Segment {
+    fn bpm_at(&self, position: f64) -> f64 {
+        let from = self.from as f64;
+        let to = self.to as f64;
+        let t = ((position - self.start) / self.length).clamp(0.0, 1.0);
+        match self.curve {
+            Curve::Instant => from,
+            Curve::Linear => from + (to - from) * t,
+            Curve::Exponential => from * (to / from).powf(t),
+        }
+    }
+
+    // NOTE: Integrates 1 / bpm over the positions (in whole notes) in closed form
+    fn integrate(&self, from: f64, to: f64) -> f64 {
+        let start = self.bpm_at(from);
+        let end = self.bpm_at(to);
+        match self.curve {
+            _ if (end - start).abs() < f64::EPSILON => (to - from) / start,
+            Curve::Instant => (to - from) / start,
+            Curve::Linear => {
+                let slope = (end - start) / (to - from);
+                (end / start).ln() / slope
+            }
+            Curve::Exponential => {
+                let rate = (self.to as f64 / self.from as f64).ln() / self.length;
+                (1.0 / start - 1.0 / end) / rate
+            }
+        }
+    }
+}
+
+// NOTE: Positions are whole notes from the start of the song and tempos count quarter notes
+#[derive(Debug, Clone, PartialEq)]
+pub struct TempoMap {
+    bpm: f32,
+    changes: Vec<TempoChange>,
+    segments: Vec<Segment>,
+}
+
+impl TempoMap {
+    const SECONDS_PER_WHOLE: f64 = 60.0 * 4.0;
+
+    pub fn new(bpm: f32) -> Self {
+        Self {
+            bpm,
+            changes: vec![],
+            segments: vec![Self::constant(0.0, f64::INFINITY, bpm)],
+        }
+    }
+
+    #[inline]
+    pub fn initial_bpm(&self) -> f32 {
+        self.bpm
+    }
+
+    #[inline]
+    pub fn changes(&self) -> &[TempoChange] {
+        &self.changes
+    }
+
+    #[inline]
+    pub fn set(&mut self, position: NoteValue, bpm: f32) {
+        self.insert(TempoChange {
+            position,
+            bpm,
+            curve: Curve::Instant,
+            length: NoteValue::ZERO,
+        });
+    }
+
+    pub fn ramp(&mut self, position: NoteValue, bpm: f32, length: NoteValue, curve: Curve) {
+        let curve = if length == NoteValue::ZERO {
+            Curve::Instant
+        } else {
+            curve
+        };
+        self.insert(TempoChange {
+            position,
+            bpm,
+            curve,
+            length,
+        });
+    }
+
+    fn insert(&mut self, change: TempoChange) {
+        self.changes
+            .retain(|existing| existing.position != change.position);
+        let index = self
+            .changes
+            .partition_point(|existing| existing.position < change.position);
+        self.changes.insert(index, change);
+        self.segments = self.build_segments();
+    }
+
+    pub fn clear_from(&mut self, position: &NoteValue) {
+        self.changes.retain(|change| change.position < *position);
+        self.segments = self.build_segments();
+    }
+
+    // NOTE: Folds the changes before the segment playing at the position into the initial tempo,
+    //       so the map only answers for that segment and later ones
+    pub fn prune(&mut self, position: &NoteValue) {
+        let position = position.as_f64();
+        let Some(segment) = self.segments.iter().find(|segment| position < segment.end) else {
+            return;
+        };
+
+        let start = segment.start;
+        let bpm = segment.from;
+        let is_ramp = segment.curve != Curve::Instant;
+        let len = self.changes.len();
+        self.changes.retain(|change| {
+            let position = change.position.as_f64();
+            position > start || (is_ramp && position == start)
+        });
+        if self.changes.len() < len {
+            self.bpm = bpm;
+            self.segments = self.build_segments();
+        }
+    }
+
+    fn build_segments(&self) -> Vec<Segment> {
+        let mut segments = vec![];
+        let mut start = 0.0;
+        let mut bpm = self.bpm;
+        let mut ramp: Option<Segment> = None;
+
+        for change in &self.changes {
+            let position = change.position.as_f64();
+            if let Some(mut segment) = ramp.take() {
+                // NOTE: A later change cuts the running ramp short
+                segment.end = segment.end.min(position);
+                bpm = segment.bpm_at(segment.end) as f32;
+                start = segment.end;
+                segments.push(segment);
+            }
+            if position > start {
+                segments.push(Self::constant(start, position, bpm));
+                start = position;
+            }
+
+            match change.curve {
+                Curve::Instant => bpm = change.bpm,
+                _ => {
+                    let length = change.length.as_f64();
+                    ramp = Some(Segment {
+                        start: position,
+                        end: position + length,
+                        from: bpm,
+                        to: change.bpm,
+                        curve: change.curve.clone(),
+                        length,
+                    });
+                }
+            }
+        }
+
+        if let Some(segment) = ramp {
+            bpm = segment.bpm_at(segment.end) as f32;
+            start = segment.end;
+            segments.push(segment);
+        }
+        segments.push(Self::constant(start, f64::INFINITY, bpm));
+        segments
+    }
+
+    fn constant(start: f64, end: f64, bpm: f32) -> Segment {
+        Segment {
+            start,
+            end,
+            from: bpm,
+            to: bpm,
+            curve: Curve::Instant,
+            length: 1.0,
+        }
+    }
+
+    pub fn bpm_at(&self, position: &NoteValue) -> f32 {
+        let position = position.as_f64();
+        self.segments
+            .iter()
+            .find(|segment| position < segment.end)
+            .map(|segment| segment.bpm_at(position) as f32)
+            .unwrap_or(self.bpm)
+    }
+
+    pub fn seconds(&self, from: &NoteValue, to: &NoteValue) -> f64 {
+        let from = from.as_f64();
+        let to = to.as_f64();
+        self.segments
+            .iter()
+            .filter(|segment| segment.start < to && from < segment.end)
+            .map(|segment| segment.integrate(segment.start.max(from), segment.end.min(to)))
+            .sum::<f64>()
+            * Self::SECONDS_PER_WHOLE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theory::Duration;
+
+    fn whole(n: u64) -> NoteValue {
+        Duration::Whole.note_value() * n
+    }
+
+    fn assert_approx(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn test_constant() {
+        let map = TempoMap::new(60.0);
+        assert_eq!(map.bpm_at(&whole(3)), 60.0);
+        assert_approx(map.seconds(&NoteValue::ZERO, &whole(1)), 4.0);
+        assert_approx(
+            map.seconds(&NoteValue::ZERO, &Duration::Eighth.note_value()),
+            0.5,
+        );
+    }
+
+    #[test]
+    fn test_set() {
+        let mut map = TempoMap::new(60.0);
+        map.set(whole(1), 120.0);
+        assert_eq!(map.bpm_at(&NoteValue::ZERO), 60.0);
+        assert_eq!(map.bpm_at(&whole(1)), 120.0);
+        assert_approx(map.seconds(&NoteValue::ZERO, &whole(2)), 6.0);
+
+        map.set(whole(1), 240.0);
+        assert_eq!(map.changes().len(), 1);
+        assert_approx(map.seconds(&NoteValue::ZERO, &whole(2)), 5.0);
+    }
+
+    #[test]
+    fn test_linear_ramp() {
+        let mut map = TempoMap::new(60.0);
+        map.ramp(whole(1), 120.0, whole(1), Curve::Linear);
+        assert_eq!(map.bpm_at(&(whole(1) + Duration::Half.note_value())), 90.0);
+        assert_eq!(map.bpm_at(&whole(3)), 120.0);
+
+        // NOTE: 240 * ln(2) / 60 seconds for the ramp measure
+        assert_approx(
+            map.seconds(&whole(1), &whole(2)),
+            240.0 * 2.0_f64.ln() / 60.0,
+        );
+        assert_approx(
+            map.seconds(&NoteValue::ZERO, &whole(3)),
+            4.0 + 240.0 * 2.0_f64.ln() / 60.0 + 2.0,
+        );
+    }
+
+    #[test]
+    fn test_exponential_ramp() {
+        let mut map = TempoMap::new(60.0);
+        map.ramp(NoteValue::ZERO, 240.0, whole(2), Curve::Exponential);
+        assert_eq!(map.bpm_at(&whole(1)), 120.0);
+        // NOTE: (1/60 - 1/240) / (ln(4) / 2) whole-note minutes
+        assert_approx(
+            map.seconds(&NoteValue::ZERO, &whole(2)),
+            240.0 * (1.0 / 60.0 - 1.0 / 240.0) / (4.0_f64.ln() / 2.0),
+        );
+    }
+
+    #[test]
+    fn test_ramp_cut() {
+        let mut map = TempoMap::new(60.0);
+        map.ramp(NoteValue::ZERO, 120.0, whole(2), Curve::Linear);
+        map.set(whole(1), 60.0);
+        assert_eq!(map.bpm_at(&Duration::Half.note_value()), 75.0);
+        assert_eq!(map.bpm_at(&whole(1)), 60.0);
+        assert_approx(map.seconds(&whole(1), &whole(2)), 4.0);
+
+        map.clear_from(&whole(1));
+        assert_eq!(map.bpm_at(&whole(2)), 120.0);
+    }
+
+    #[test]
+    fn test_prune() {
+        let mut map = TempoMap::new(60.0);
+        map.set(whole(1), 120.0);
+        map.ramp(whole(2), 240.0, whole(2), Curve::Linear);
+        map.set(whole(5), 60.0);
+        let seconds = map.seconds(&whole(3), &whole(6));
+
+        // NOTE: The running ramp is kept, the change before it is folded into the initial tempo
+        map.prune(&whole(3));
+        assert_eq!(map.initial_bpm(), 120.0);
+        assert_eq!(map.changes().len(), 2);
+        assert_eq!(map.bpm_at(&whole(3)), 180.0);
+        assert_approx(map.seconds(&whole(3), &whole(6)), seconds);
+
+        map.prune(&whole(4));
+        assert_eq!(map.initial_bpm(), 240.0);
+        assert_eq!(map.changes().len(), 1);
+        assert_approx(map.seconds(&whole(4), &whole(6)), 1.0 + 4.0);
+
+        map.prune(&whole(5));
+        assert_eq!(map.initial_bpm(), 60.0);
+        assert!(map.changes().is_empty());
+        assert_eq!(map.bpm_at(&whole(6)), 60.0);
+    }
+}
@@ -1,18 +1,42 @@
 mod chord;
+mod chord_candidate;
 mod chord_like;
+mod chord_symbol;
 mod duration;
 mod interval;
 mod inversion;
+mod key;
 mod note;
+mod note_value;
+mod parse_error;
 mod pitch_class;
 mod quality;
+mod scala;
+mod scale;
+mod slash;
+mod spelling;
+mod time_signature;
+mod tuning;
+mod voice_leading;
 
 pub use chord::*;
+pub use chord_candidate::*;
 pub use chord_like::*;
+pub use chord_symbol::*;
 pub use duration::*;
 pub use interval::*;
 #[allow(unused_imports)]
 pub use inversion::*;
+pub use key::*;
 pub use note::*;
+pub use note_value::*;
+pub use parse_error::*;
 pub use pitch_class::*;
 pub use quality::*;
+pub use scala::*;
+pub use scale::*;
+pub use slash::*;
+pub use spelling::*;
+pub use time_signature::*;
+pub use tuning::*;
+pub use voice_leading::*;
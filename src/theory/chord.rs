@@ -1,25 +1,89 @@
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
-use super::{ChordLike, Interval, Note};
+use super::{
+    ChordLike, Interval, Note, ParseError, ParseErrorKind, PitchClass, Spelled, SpelledNote,
+    SpelledPitchClass,
+};
 
-#[allow(dead_code)]
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum Chord {
-    Major(Note),
-    Minor(Note),
-    Major7th(Note),
-    Minor7th(Note),
-    Major9th(Note),
-    Minor9th(Note),
+// NOTE: Generates the constructor list from the variants so that no chord type is left out
+macro_rules! chords {
+    ($($variant:ident),+ $(,)?) => {
+        #[allow(dead_code)]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum Chord {
+            $($variant(Note)),+
+        }
+
+        impl Chord {
+            pub(crate) const CONSTRUCTORS: &'static [fn(Note) -> Chord] = &[$(Chord::$variant),+];
+        }
+    };
+}
+
+chords! {
+    Major,
+    Minor,
+    Diminished,
+    Augmented,
+    Sus2,
+    Sus4,
+    Major7th,
+    Minor7th,
+    Dominant7th,
+    HalfDiminished7th,
+    Diminished7th,
+    MinorMajor7th,
+    AugmentedMajor7th,
+    Major9th,
+    Minor9th,
+    Dominant9th,
+    Dominant7thFlat9,
+    Major6th,
+    Minor6th,
+    Dominant7thSus4,
+    Dominant7thFlat5,
+    Dominant7thSharp5,
+    Dominant7thSharp9,
+    Dominant7thSharp11,
+    Altered,
+    Dominant11th,
+    Minor11th,
+    Dominant13th,
+    Major13th,
+    Minor13th,
+    Add9,
+    Add11,
+    MinorAdd9,
 }
 
 impl Chord {
+    pub const DEFAULT_OCTAVE: i8 = 4;
+
     pub fn intervals(&self) -> Vec<Interval> {
         match self {
             Chord::Major(_) => vec![Interval::P1, Interval::Maj3, Interval::P5],
             Chord::Minor(_) => vec![Interval::P1, Interval::Min3, Interval::P5],
+            Chord::Diminished(_) => vec![Interval::P1, Interval::Min3, Interval::D5],
+            Chord::Augmented(_) => vec![Interval::P1, Interval::Maj3, Interval::A5],
+            Chord::Sus2(_) => vec![Interval::P1, Interval::Maj2, Interval::P5],
+            Chord::Sus4(_) => vec![Interval::P1, Interval::P4, Interval::P5],
             Chord::Major7th(_) => vec![Interval::P1, Interval::Maj3, Interval::P5, Interval::Maj7],
             Chord::Minor7th(_) => vec![Interval::P1, Interval::Min3, Interval::P5, Interval::Min7],
+            Chord::Dominant7th(_) => {
+                vec![Interval::P1, Interval::Maj3, Interval::P5, Interval::Min7]
+            }
+            Chord::HalfDiminished7th(_) => {
+                vec![Interval::P1, Interval::Min3, Interval::D5, Interval::Min7]
+            }
+            Chord::Diminished7th(_) => {
+                vec![Interval::P1, Interval::Min3, Interval::D5, Interval::D7]
+            }
+            Chord::MinorMajor7th(_) => {
+                vec![Interval::P1, Interval::Min3, Interval::P5, Interval::Maj7]
+            }
+            Chord::AugmentedMajor7th(_) => {
+                vec![Interval::P1, Interval::Maj3, Interval::A5, Interval::Maj7]
+            }
             Chord::Major9th(_) => vec![
                 Interval::P1,
                 Interval::Maj3,
@@ -34,18 +98,287 @@ impl Chord {
                 Interval::Min7,
                 Interval::Maj9,
             ],
+            Chord::Dominant9th(_) => vec![
+                Interval::P1,
+                Interval::Maj3,
+                Interval::P5,
+                Interval::Min7,
+                Interval::Maj9,
+            ],
+            Chord::Dominant7thFlat9(_) => vec![
+                Interval::P1,
+                Interval::Maj3,
+                Interval::P5,
+                Interval::Min7,
+                Interval::Min9,
+            ],
+            Chord::Major6th(_) => vec![Interval::P1, Interval::Maj3, Interval::P5, Interval::Maj6],
+            Chord::Minor6th(_) => vec![Interval::P1, Interval::Min3, Interval::P5, Interval::Maj6],
+            Chord::Dominant7thSus4(_) => {
+                vec![Interval::P1, Interval::P4, Interval::P5, Interval::Min7]
+            }
+            Chord::Dominant7thFlat5(_) => {
+                vec![Interval::P1, Interval::Maj3, Interval::D5, Interval::Min7]
+            }
+            Chord::Dominant7thSharp5(_) => {
+                vec![Interval::P1, Interval::Maj3, Interval::A5, Interval::Min7]
+            }
+            Chord::Dominant7thSharp9(_) => vec![
+                Interval::P1,
+                Interval::Maj3,
+                Interval::P5,
+                Interval::Min7,
+                Interval::A9,
+            ],
+            Chord::Dominant7thSharp11(_) => vec![
+                Interval::P1,
+                Interval::Maj3,
+                Interval::P5,
+                Interval::Min7,
+                Interval::A11,
+            ],
+            Chord::Altered(_) => vec![
+                Interval::P1,
+                Interval::Maj3,
+                Interval::Min7,
+                Interval::Min9,
+                Interval::A9,
+                Interval::A11,
+                Interval::Min13,
+            ],
+            Chord::Dominant11th(_) => vec![
+                Interval::P1,
+                Interval::Maj3,
+                Interval::P5,
+                Interval::Min7,
+                Interval::Maj9,
+                Interval::P11,
+            ],
+            Chord::Minor11th(_) => vec![
+                Interval::P1,
+                Interval::Min3,
+                Interval::P5,
+                Interval::Min7,
+                Interval::Maj9,
+                Interval::P11,
+            ],
+            Chord::Dominant13th(_) => vec![
+                Interval::P1,
+                Interval::Maj3,
+                Interval::P5,
+                Interval::Min7,
+                Interval::Maj9,
+                Interval::Maj13,
+            ],
+            Chord::Major13th(_) => vec![
+                Interval::P1,
+                Interval::Maj3,
+                Interval::P5,
+                Interval::Maj7,
+                Interval::Maj9,
+                Interval::Maj13,
+            ],
+            Chord::Minor13th(_) => vec![
+                Interval::P1,
+                Interval::Min3,
+                Interval::P5,
+                Interval::Min7,
+                Interval::Maj9,
+                Interval::P11,
+                Interval::Maj13,
+            ],
+            Chord::Add9(_) => vec![Interval::P1, Interval::Maj3, Interval::P5, Interval::Maj9],
+            Chord::Add11(_) => vec![Interval::P1, Interval::Maj3, Interval::P5, Interval::P11],
+            Chord::MinorAdd9(_) => vec![Interval::P1, Interval::Min3, Interval::P5, Interval::Maj9],
+        }
+    }
+
+    pub fn parse(s: &str, octave: i8) -> Result<Self, ParseError> {
+        if s.is_empty() {
+            return Err(ParseError::new(ParseErrorKind::Empty, "empty chord"));
         }
+        if s.contains('/') {
+            return Err(ParseError::new(
+                ParseErrorKind::InvalidBass,
+                format!("bass note is not supported by chord, parse a ChordSymbol instead: {s}"),
+            ));
+        }
+
+        let (pitch_class, accidental, suffix) = Self::parse_root(s).ok_or_else(|| {
+            ParseError::new(ParseErrorKind::InvalidRoot, format!("invalid root: {s}"))
+        })?;
+        let root = Note::from_pitch_class(&pitch_class, octave)
+            .and_then(|note| note.checked_transpose(accidental))
+            .ok_or_else(|| {
+                ParseError::new(
+                    ParseErrorKind::OutOfRange,
+                    format!("octave out of range: {octave}"),
+                )
+            })?;
+
+        let chord = match suffix {
+            "" | "M" | "maj" | "Δ" => Chord::Major(root),
+            "m" | "min" | "-" => Chord::Minor(root),
+            "dim" | "°" | "o" => Chord::Diminished(root),
+            "aug" | "+" => Chord::Augmented(root),
+            "sus2" => Chord::Sus2(root),
+            "sus4" | "sus" => Chord::Sus4(root),
+            "M7" | "maj7" | "Δ7" => Chord::Major7th(root),
+            "m7" | "min7" | "-7" => Chord::Minor7th(root),
+            "7" | "dom7" => Chord::Dominant7th(root),
+            "m7b5" | "-7b5" | "ø" | "ø7" => Chord::HalfDiminished7th(root),
+            "dim7" | "°7" | "o7" => Chord::Diminished7th(root),
+            "mM7" | "m(maj7)" | "minmaj7" | "-M7" => Chord::MinorMajor7th(root),
+            "augM7" | "augmaj7" | "+M7" | "maj7#5" | "M7#5" => Chord::AugmentedMajor7th(root),
+            "M9" | "maj9" | "Δ9" => Chord::Major9th(root),
+            "m9" | "min9" | "-9" => Chord::Minor9th(root),
+            "9" => Chord::Dominant9th(root),
+            "7b9" => Chord::Dominant7thFlat9(root),
+            "6" | "M6" | "maj6" => Chord::Major6th(root),
+            "m6" | "min6" | "-6" => Chord::Minor6th(root),
+            "7sus4" | "7sus" => Chord::Dominant7thSus4(root),
+            "7b5" => Chord::Dominant7thFlat5(root),
+            "7#5" | "aug7" | "+7" => Chord::Dominant7thSharp5(root),
+            "7#9" => Chord::Dominant7thSharp9(root),
+            "7#11" => Chord::Dominant7thSharp11(root),
+            "7alt" | "alt" => Chord::Altered(root),
+            "11" => Chord::Dominant11th(root),
+            "m11" | "min11" | "-11" => Chord::Minor11th(root),
+            "13" => Chord::Dominant13th(root),
+            "M13" | "maj13" | "Δ13" => Chord::Major13th(root),
+            "m13" | "min13" | "-13" => Chord::Minor13th(root),
+            "add9" | "add2" => Chord::Add9(root),
+            "add11" | "add4" => Chord::Add11(root),
+            "madd9" | "m(add9)" | "-add9" => Chord::MinorAdd9(root),
+            _ => {
+                return Err(ParseError::new(
+                    ParseErrorKind::InvalidQuality,
+                    format!("invalid quality: {suffix}"),
+                ))
+            }
+        };
+        Ok(chord)
+    }
+
+    pub fn spelled_root(&self) -> SpelledPitchClass {
+        let intervals = self.intervals();
+        SpelledPitchClass::simplest(&self.root_note().pitch_class(), |root| {
+            intervals
+                .iter()
+                .map(|interval| root.transpose(interval))
+                .collect()
+        })
+    }
+
+    // NOTE: Chord tones keep their spelling, other notes follow the chord's accidentals, e.g. the
+    //       bass of a slash chord
+    pub fn spell(&self, pitch_class: &PitchClass) -> SpelledPitchClass {
+        let root = self.spelled_root();
+        let tones: Vec<SpelledPitchClass> = self
+            .intervals()
+            .iter()
+            .filter_map(|interval| root.transpose(interval))
+            .collect();
+        if let Some(tone) = tones.iter().find(|tone| tone.pitch_class() == *pitch_class) {
+            return tone.clone();
+        }
+
+        if tones.iter().any(|tone| tone.accidental().offset() < 0) {
+            SpelledPitchClass::flat(pitch_class)
+        } else if tones.iter().any(|tone| tone.accidental().offset() > 0) {
+            SpelledPitchClass::sharp(pitch_class)
+        } else {
+            SpelledPitchClass::simplest(pitch_class, |_| vec![])
+        }
+    }
+
+    pub fn spelled_notes(&self) -> Vec<SpelledNote> {
+        let root = SpelledNote::from_note(&self.root_note(), &self.spelled_root())
+            .expect("should be spelled with its own pitch class");
+        self.intervals()
+            .iter()
+            .filter_map(|interval| root.transpose(interval))
+            .collect()
+    }
+
+    fn suffix(&self) -> &'static str {
+        match self {
+            Chord::Major(_) => "",
+            Chord::Minor(_) => "m",
+            Chord::Diminished(_) => "dim",
+            Chord::Augmented(_) => "aug",
+            Chord::Sus2(_) => "sus2",
+            Chord::Sus4(_) => "sus4",
+            Chord::Major7th(_) => "M7",
+            Chord::Minor7th(_) => "m7",
+            Chord::Dominant7th(_) => "7",
+            Chord::HalfDiminished7th(_) => "m7b5",
+            Chord::Diminished7th(_) => "dim7",
+            Chord::MinorMajor7th(_) => "mM7",
+            Chord::AugmentedMajor7th(_) => "augM7",
+            Chord::Major9th(_) => "M9",
+            Chord::Minor9th(_) => "m9",
+            Chord::Dominant9th(_) => "9",
+            Chord::Dominant7thFlat9(_) => "7b9",
+            Chord::Major6th(_) => "6",
+            Chord::Minor6th(_) => "m6",
+            Chord::Dominant7thSus4(_) => "7sus4",
+            Chord::Dominant7thFlat5(_) => "7b5",
+            Chord::Dominant7thSharp5(_) => "7#5",
+            Chord::Dominant7thSharp9(_) => "7#9",
+            Chord::Dominant7thSharp11(_) => "7#11",
+            Chord::Altered(_) => "7alt",
+            Chord::Dominant11th(_) => "11",
+            Chord::Minor11th(_) => "m11",
+            Chord::Dominant13th(_) => "13",
+            Chord::Major13th(_) => "M13",
+            Chord::Minor13th(_) => "m13",
+            Chord::Add9(_) => "add9",
+            Chord::Add11(_) => "add11",
+            Chord::MinorAdd9(_) => "madd9",
+        }
+    }
+
+    // NOTE: Accidentals are returned separately so that e.g. Cb4 resolves to B3
+    pub(crate) fn parse_root(s: &str) -> Option<(PitchClass, i8, &str)> {
+        let mut chars = s.chars();
+        let natural = match chars.next()? {
+            'C' => PitchClass::C,
+            'D' => PitchClass::D,
+            'E' => PitchClass::E,
+            'F' => PitchClass::F,
+            'G' => PitchClass::G,
+            'A' => PitchClass::A,
+            'B' => PitchClass::B,
+            _ => return None,
+        };
+        let rest = chars.as_str();
+        if let Some(rest) = rest.strip_prefix(['#', '♯']) {
+            Some((natural, 1, rest))
+        } else if let Some(rest) = rest.strip_prefix(['b', '♭']) {
+            Some((natural, -1, rest))
+        } else {
+            Some((natural, 0, rest))
+        }
+    }
+}
+
+impl FromStr for Chord {
+    type Err = ParseError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, Self::DEFAULT_OCTAVE)
     }
 }
 
 #[allow(dead_code)]
 impl ChordLike for Chord {
     fn notes(&self) -> Vec<Note> {
+        let root = self.root_note();
         self.intervals()
             .iter()
-            .filter_map(|interval| {
-                Note::from_note_number(self.root_note().note_number() + interval.semitones())
-            })
+            .filter_map(|interval| &root + interval)
             .collect()
     }
 
@@ -53,25 +386,51 @@ impl ChordLike for Chord {
         match self {
             Chord::Major(note)
             | Chord::Minor(note)
+            | Chord::Diminished(note)
+            | Chord::Augmented(note)
+            | Chord::Sus2(note)
+            | Chord::Sus4(note)
             | Chord::Major7th(note)
             | Chord::Minor7th(note)
+            | Chord::Dominant7th(note)
+            | Chord::HalfDiminished7th(note)
+            | Chord::Diminished7th(note)
+            | Chord::MinorMajor7th(note)
+            | Chord::AugmentedMajor7th(note)
             | Chord::Major9th(note)
-            | Chord::Minor9th(note) => note.clone(),
+            | Chord::Minor9th(note)
+            | Chord::Dominant9th(note)
+            | Chord::Dominant7thFlat9(note)
+            | Chord::Major6th(note)
+            | Chord::Minor6th(note)
+            | Chord::Dominant7thSus4(note)
+            | Chord::Dominant7thFlat5(note)
+            | Chord::Dominant7thSharp5(note)
+            | Chord::Dominant7thSharp9(note)
+            | Chord::Dominant7thSharp11(note)
+            | Chord::Altered(note)
+            | Chord::Dominant11th(note)
+            | Chord::Minor11th(note)
+            | Chord::Dominant13th(note)
+            | Chord::Major13th(note)
+            | Chord::Minor13th(note)
+            | Chord::Add9(note)
+            | Chord::Add11(note)
+            | Chord::MinorAdd9(note) => note.clone(),
         }
     }
 }
 
 impl Display for Chord {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let s = match self {
-            Chord::Major(note) => note.pitch_class().to_string(),
-            Chord::Minor(note) => format!("{}m", note.pitch_class()),
-            Chord::Major7th(note) => format!("{}M7", note.pitch_class()),
-            Chord::Minor7th(note) => format!("{}m7", note.pitch_class()),
-            Chord::Major9th(note) => format!("{}M9", note.pitch_class()),
-            Chord::Minor9th(note) => format!("{}m9", note.pitch_class()),
-        };
-        write!(f, "{s}")
+        write!(f, "{}{}", self.root_note().pitch_class(), self.suffix())
+    }
+}
+
+// NOTE: Spells the root from the chord's own tones, e.g. Bbm7 rather than A#m7
+impl Display for Spelled<'_, Chord> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.0.spelled_root(), self.0.suffix())
     }
 }
 
@@ -79,6 +438,25 @@ impl Display for Chord {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_spelled_notes() {
+        let spelled = |chord: Chord| {
+            chord
+                .spelled_notes()
+                .iter()
+                .map(|note| note.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        assert_eq!(spelled(Chord::Minor(Note::F3)), "F3 Ab3 C4");
+        assert_eq!(spelled(Chord::Minor(Note::Csharp3)), "C#3 E3 G#3");
+        assert_eq!(spelled(Chord::Dominant7th(Note::Asharp3)), "Bb3 D4 F4 Ab4");
+        assert_eq!(spelled(Chord::Diminished7th(Note::C3)), "C3 Eb3 Gb3 Bbb3");
+        assert_eq!(spelled(Chord::Augmented(Note::C3)), "C3 E3 G#3");
+        assert_eq!(spelled(Chord::Major(Note::Fsharp3)), "F#3 A#3 C#4");
+        assert_eq!(spelled(Chord::Major9th(Note::Dsharp3)), "Eb3 G3 Bb3 D4 F4");
+    }
+
     #[test]
     fn test_display() {
         assert_eq!(Chord::Major(Note::C3).to_string(), "C");
@@ -87,6 +465,35 @@ mod tests {
         assert_eq!(Chord::Minor7th(Note::C3).to_string(), "Cm7");
         assert_eq!(Chord::Major9th(Note::C3).to_string(), "CM9");
         assert_eq!(Chord::Minor9th(Note::C3).to_string(), "Cm9");
+        assert_eq!(Chord::Diminished(Note::B3).to_string(), "Bdim");
+        assert_eq!(Chord::Augmented(Note::C3).to_string(), "Caug");
+        assert_eq!(Chord::Dominant7th(Note::G3).to_string(), "G7");
+        assert_eq!(Chord::HalfDiminished7th(Note::B3).to_string(), "Bm7b5");
+        assert_eq!(Chord::Diminished7th(Note::B3).to_string(), "Bdim7");
+        assert_eq!(Chord::MinorMajor7th(Note::C3).to_string(), "CmM7");
+        assert_eq!(Chord::AugmentedMajor7th(Note::C3).to_string(), "CaugM7");
+        assert_eq!(Chord::Dominant9th(Note::G3).to_string(), "G9");
+        assert_eq!(Chord::Sus2(Note::C3).to_string(), "Csus2");
+        assert_eq!(Chord::Sus4(Note::C3).to_string(), "Csus4");
+        assert_eq!(Chord::Dominant7thFlat9(Note::G3).to_string(), "G7b9");
+        assert_eq!(Chord::Major6th(Note::C3).to_string(), "C6");
+        assert_eq!(Chord::Minor6th(Note::C3).to_string(), "Cm6");
+        assert_eq!(Chord::Dominant7thSus4(Note::G3).to_string(), "G7sus4");
+        assert_eq!(Chord::Dominant7thSharp9(Note::E3).to_string(), "E7#9");
+        assert_eq!(Chord::Altered(Note::G3).to_string(), "G7alt");
+        assert_eq!(Chord::Dominant11th(Note::C3).to_string(), "C11");
+        assert_eq!(Chord::Major13th(Note::C3).to_string(), "CM13");
+        assert_eq!(Chord::Add9(Note::C3).to_string(), "Cadd9");
+        assert_eq!(Chord::MinorAdd9(Note::C3).to_string(), "Cmadd9");
+    }
+
+    #[test]
+    fn test_spelled_display() {
+        assert_eq!(Chord::Minor7th(Note::Asharp3).to_string(), "A#m7");
+        assert_eq!(Spelled(&Chord::Minor7th(Note::Asharp3)).to_string(), "Bbm7");
+        assert_eq!(Spelled(&Chord::Major(Note::Csharp4)).to_string(), "Db");
+        assert_eq!(Spelled(&Chord::Major(Note::Fsharp3)).to_string(), "F#");
+        assert_eq!(Spelled(&Chord::Minor(Note::Csharp3)).to_string(), "C#m");
     }
 
     #[test]
@@ -115,5 +522,219 @@ mod tests {
             Chord::Minor9th(Note::C3).notes(),
             vec![Note::C3, Note::Dsharp3, Note::G3, Note::Asharp3, Note::D4]
         );
+        assert_eq!(
+            Chord::Major9th(Note::C5).notes(),
+            vec![Note::C5, Note::E5, Note::G5, Note::B5, Note::D6]
+        );
+        assert_eq!(
+            Chord::Diminished(Note::B3).notes(),
+            vec![Note::B3, Note::D4, Note::F4]
+        );
+        assert_eq!(
+            Chord::Augmented(Note::C3).notes(),
+            vec![Note::C3, Note::E3, Note::Gsharp3]
+        );
+        assert_eq!(
+            Chord::Dominant7th(Note::G3).notes(),
+            vec![Note::G3, Note::B3, Note::D4, Note::F4]
+        );
+        assert_eq!(
+            Chord::HalfDiminished7th(Note::B3).notes(),
+            vec![Note::B3, Note::D4, Note::F4, Note::A4]
+        );
+        assert_eq!(
+            Chord::Diminished7th(Note::B3).notes(),
+            vec![Note::B3, Note::D4, Note::F4, Note::Gsharp4]
+        );
+        assert_eq!(
+            Chord::MinorMajor7th(Note::C3).notes(),
+            vec![Note::C3, Note::Dsharp3, Note::G3, Note::B3]
+        );
+        assert_eq!(
+            Chord::AugmentedMajor7th(Note::C3).notes(),
+            vec![Note::C3, Note::E3, Note::Gsharp3, Note::B3]
+        );
+        assert_eq!(
+            Chord::Dominant9th(Note::G3).notes(),
+            vec![Note::G3, Note::B3, Note::D4, Note::F4, Note::A4]
+        );
+        assert_eq!(
+            Chord::Sus2(Note::C3).notes(),
+            vec![Note::C3, Note::D3, Note::G3]
+        );
+        assert_eq!(
+            Chord::Sus4(Note::C3).notes(),
+            vec![Note::C3, Note::F3, Note::G3]
+        );
+        assert_eq!(
+            Chord::Dominant7thFlat9(Note::G3).notes(),
+            vec![Note::G3, Note::B3, Note::D4, Note::F4, Note::Gsharp4]
+        );
+    }
+
+    #[test]
+    fn test_notes_extended() {
+        assert_eq!(
+            Chord::Major6th(Note::C3).notes(),
+            vec![Note::C3, Note::E3, Note::G3, Note::A3]
+        );
+        assert_eq!(
+            Chord::Minor6th(Note::C3).notes(),
+            vec![Note::C3, Note::Dsharp3, Note::G3, Note::A3]
+        );
+        assert_eq!(
+            Chord::Dominant7thSus4(Note::G3).notes(),
+            vec![Note::G3, Note::C4, Note::D4, Note::F4]
+        );
+        assert_eq!(
+            Chord::Dominant7thFlat5(Note::G3).notes(),
+            vec![Note::G3, Note::B3, Note::Csharp4, Note::F4]
+        );
+        assert_eq!(
+            Chord::Dominant7thSharp5(Note::G3).notes(),
+            vec![Note::G3, Note::B3, Note::Dsharp4, Note::F4]
+        );
+        assert_eq!(
+            Chord::Dominant7thSharp9(Note::E3).notes(),
+            vec![Note::E3, Note::Gsharp3, Note::B3, Note::D4, Note::G4]
+        );
+        assert_eq!(
+            Chord::Dominant7thSharp11(Note::C3).notes(),
+            vec![Note::C3, Note::E3, Note::G3, Note::Asharp3, Note::Fsharp4]
+        );
+        assert_eq!(
+            Chord::Altered(Note::G3).notes(),
+            vec![
+                Note::G3,
+                Note::B3,
+                Note::F4,
+                Note::Gsharp4,
+                Note::Asharp4,
+                Note::Csharp5,
+                Note::Dsharp5
+            ]
+        );
+        assert_eq!(
+            Chord::Dominant11th(Note::C3).notes(),
+            vec![
+                Note::C3,
+                Note::E3,
+                Note::G3,
+                Note::Asharp3,
+                Note::D4,
+                Note::F4
+            ]
+        );
+        assert_eq!(
+            Chord::Minor11th(Note::A2).notes(),
+            vec![Note::A2, Note::C3, Note::E3, Note::G3, Note::B3, Note::D4]
+        );
+        assert_eq!(
+            Chord::Dominant13th(Note::G3).notes(),
+            vec![Note::G3, Note::B3, Note::D4, Note::F4, Note::A4, Note::E5]
+        );
+        assert_eq!(
+            Chord::Major13th(Note::C3).notes(),
+            vec![Note::C3, Note::E3, Note::G3, Note::B3, Note::D4, Note::A4]
+        );
+        assert_eq!(
+            Chord::Minor13th(Note::D3).notes(),
+            vec![
+                Note::D3,
+                Note::F3,
+                Note::A3,
+                Note::C4,
+                Note::E4,
+                Note::G4,
+                Note::B4
+            ]
+        );
+        assert_eq!(
+            Chord::Add9(Note::C3).notes(),
+            vec![Note::C3, Note::E3, Note::G3, Note::D4]
+        );
+        assert_eq!(
+            Chord::Add11(Note::C3).notes(),
+            vec![Note::C3, Note::E3, Note::G3, Note::F4]
+        );
+        assert_eq!(
+            Chord::MinorAdd9(Note::C3).notes(),
+            vec![Note::C3, Note::Dsharp3, Note::G3, Note::D4]
+        );
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("C".parse(), Ok(Chord::Major(Note::C4)));
+        assert_eq!("Cm".parse(), Ok(Chord::Minor(Note::C4)));
+        assert_eq!("Cmaj7".parse(), Ok(Chord::Major7th(Note::C4)));
+        assert_eq!("CM7".parse(), Ok(Chord::Major7th(Note::C4)));
+        assert_eq!("C-7".parse(), Ok(Chord::Minor7th(Note::C4)));
+        assert_eq!("Cdim".parse(), Ok(Chord::Diminished(Note::C4)));
+        assert_eq!("C+".parse(), Ok(Chord::Augmented(Note::C4)));
+        assert_eq!("Csus4".parse(), Ok(Chord::Sus4(Note::C4)));
+        assert_eq!("C7b9".parse(), Ok(Chord::Dominant7thFlat9(Note::C4)));
+        assert_eq!(
+            "Bbm7b5".parse(),
+            Ok(Chord::HalfDiminished7th(Note::Asharp4))
+        );
+        assert_eq!("F#7".parse(), Ok(Chord::Dominant7th(Note::Fsharp4)));
+        assert_eq!("Cb".parse(), Ok(Chord::Major(Note::B3)));
+        assert_eq!("B#".parse(), Ok(Chord::Major(Note::C5)));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Chord::parse("Am7", 2), Ok(Chord::Minor7th(Note::A2)));
+        assert_eq!(
+            Chord::parse("G9", -1),
+            Ok(Chord::Dominant9th(Note::Gminus1))
+        );
+        for chord in [
+            Chord::Major(Note::D3),
+            Chord::Minor9th(Note::E3),
+            Chord::MinorMajor7th(Note::F3),
+            Chord::AugmentedMajor7th(Note::G3),
+            Chord::Diminished7th(Note::A3),
+            Chord::Sus2(Note::B3),
+            Chord::Major6th(Note::C3),
+            Chord::Minor6th(Note::C3),
+            Chord::Dominant7thSus4(Note::C3),
+            Chord::Dominant7thFlat5(Note::C3),
+            Chord::Dominant7thSharp5(Note::C3),
+            Chord::Dominant7thSharp9(Note::C3),
+            Chord::Dominant7thSharp11(Note::C3),
+            Chord::Altered(Note::C3),
+            Chord::Dominant11th(Note::C3),
+            Chord::Minor11th(Note::C3),
+            Chord::Dominant13th(Note::C3),
+            Chord::Major13th(Note::C3),
+            Chord::Minor13th(Note::C3),
+            Chord::Add9(Note::C3),
+            Chord::Add11(Note::C3),
+            Chord::MinorAdd9(Note::C3),
+        ] {
+            assert_eq!(Chord::parse(&chord.to_string(), 3), Ok(chord));
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Chord::parse("", 4).unwrap_err().kind(),
+            &ParseErrorKind::Empty
+        );
+        assert_eq!(
+            Chord::parse("H7", 4).unwrap_err(),
+            ParseError::new(ParseErrorKind::InvalidRoot, "invalid root: H7")
+        );
+        assert_eq!(
+            Chord::parse("Cfoo", 4).unwrap_err(),
+            ParseError::new(ParseErrorKind::InvalidQuality, "invalid quality: foo")
+        );
+        assert_eq!(
+            Chord::parse("A", 9).unwrap_err().kind(),
+            &ParseErrorKind::OutOfRange
+        );
     }
 }
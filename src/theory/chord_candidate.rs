@@ -0,0 +1,304 @@
+use std::{collections::BTreeSet, fmt::Display};
+
+use super::{Chord, ChordLike, Interval, Note, PitchClass, Slash};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChordCandidate {
+    chord: Chord,
+    inversion: usize,
+    bass: Option<Note>,
+    missing: Vec<Interval>,
+    added: Vec<PitchClass>,
+}
+
+impl ChordCandidate {
+    const SEMITONES: u8 = 12;
+
+    // NOTE: The first note is treated as the bass, as in ChordLike::notes
+    pub fn from_notes(notes: &[Note]) -> Vec<Self> {
+        let Some(bass) = notes.first() else {
+            return vec![];
+        };
+        let pitch_classes = notes
+            .iter()
+            .map(|note| note.note_number() % Self::SEMITONES)
+            .collect();
+        Self::candidates(&pitch_classes, Some(bass))
+    }
+
+    pub fn from_pitch_classes(pitch_classes: &[PitchClass]) -> Vec<Self> {
+        let pitch_classes = pitch_classes
+            .iter()
+            .map(|pitch_class| pitch_class.semitones())
+            .collect();
+        Self::candidates(&pitch_classes, None)
+    }
+
+    #[inline]
+    pub fn chord(&self) -> &Chord {
+        &self.chord
+    }
+
+    #[inline]
+    pub fn inversion(&self) -> usize {
+        self.inversion
+    }
+
+    // NOTE: The bass note when it is not a chord tone, as in a slash chord
+    #[inline]
+    pub fn bass(&self) -> Option<&Note> {
+        self.bass.as_ref()
+    }
+
+    #[inline]
+    pub fn missing(&self) -> &[Interval] {
+        &self.missing
+    }
+
+    #[inline]
+    pub fn added(&self) -> &[PitchClass] {
+        &self.added
+    }
+
+    fn candidates(pitch_classes: &BTreeSet<u8>, bass: Option<&Note>) -> Vec<Self> {
+        let shapes: Vec<Vec<Interval>> = Chord::CONSTRUCTORS
+            .iter()
+            .map(|constructor| constructor(Note::C4).intervals())
+            .collect();
+        let mut ranked = vec![];
+        for root in 0..Self::SEMITONES {
+            for (order, (constructor, intervals)) in
+                Chord::CONSTRUCTORS.iter().zip(&shapes).enumerate()
+            {
+                let tone = |interval: &Interval| (root + interval.semitones()) % Self::SEMITONES;
+                let matched = intervals
+                    .iter()
+                    .filter(|interval| pitch_classes.contains(&tone(interval)))
+                    .count();
+                if matched < 2 || matched * 2 < intervals.len() {
+                    continue;
+                }
+
+                let tones: Vec<u8> = intervals.iter().map(tone).collect();
+
+                let missing: Vec<Interval> = intervals
+                    .iter()
+                    .zip(&tones)
+                    .filter(|(_, tone)| !pitch_classes.contains(tone))
+                    .map(|(interval, _)| interval.clone())
+                    .collect();
+                let added: Vec<PitchClass> = pitch_classes
+                    .iter()
+                    .filter(|pitch_class| !tones.contains(pitch_class))
+                    .map(|pitch_class| PitchClass::from_semitones(*pitch_class))
+                    .collect();
+
+                let (root_note, inversion, slash_bass) = match bass {
+                    Some(bass) => {
+                        let bass_pitch_class = bass.note_number() % Self::SEMITONES;
+                        match tones.iter().position(|tone| *tone == bass_pitch_class) {
+                            Some(position) => (
+                                bass.checked_transpose(-(intervals[position].semitones() as i8)),
+                                position,
+                                None,
+                            ),
+                            // NOTE: The chord sits above a foreign bass, as in Slash::notes
+                            None => {
+                                let offset =
+                                    (root + Self::SEMITONES - bass_pitch_class) % Self::SEMITONES;
+                                (bass.checked_transpose(offset as i8), 0, Some(bass.clone()))
+                            }
+                        }
+                    }
+                    None => (
+                        Note::from_pitch_class(
+                            &PitchClass::from_semitones(root),
+                            Chord::DEFAULT_OCTAVE,
+                        ),
+                        0,
+                        None,
+                    ),
+                };
+                let Some(root_note) = root_note else {
+                    continue;
+                };
+
+                let rank = (
+                    added.len() + missing.len(),
+                    missing.len(),
+                    inversion > 0 || slash_bass.is_some(),
+                    inversion,
+                    tones.len(),
+                    order,
+                    root,
+                );
+                let candidate = Self {
+                    chord: constructor(root_note),
+                    inversion,
+                    bass: slash_bass,
+                    missing,
+                    added,
+                };
+                ranked.push((rank, candidate));
+            }
+        }
+
+        ranked.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+        ranked.into_iter().map(|(_, candidate)| candidate).collect()
+    }
+}
+
+impl ChordLike for ChordCandidate {
+    #[inline]
+    fn notes(&self) -> Vec<Note> {
+        match &self.bass {
+            Some(bass) => Slash(self.chord.clone(), bass.clone()).notes(),
+            None => self.chord.inverted(self.inversion),
+        }
+    }
+
+    #[inline]
+    fn root_note(&self) -> Note {
+        self.chord.root_note()
+    }
+}
+
+impl Display for ChordCandidate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.bass {
+            Some(bass) => write!(f, "{}", Slash(self.chord.clone(), bass.clone())),
+            None => write!(f, "{}", self.chord),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::theory::{Inversion, Slash};
+
+    use super::*;
+
+    fn pitch_classes(notes: &[Note]) -> BTreeSet<PitchClass> {
+        notes.iter().map(|note| note.pitch_class()).collect()
+    }
+
+    #[test]
+    fn test_from_notes() {
+        let candidates = ChordCandidate::from_notes(&[Note::C3, Note::E3, Note::G3]);
+        let first = candidates.first().unwrap();
+        assert_eq!(first.chord(), &Chord::Major(Note::C3));
+        assert_eq!(first.inversion(), 0);
+        assert!(first.missing().is_empty());
+        assert!(first.added().is_empty());
+        assert_eq!(first.to_string(), "C");
+    }
+
+    #[test]
+    fn test_from_notes_empty() {
+        assert_eq!(ChordCandidate::from_notes(&[]), vec![]);
+    }
+
+    #[test]
+    fn test_from_notes_missing() {
+        let candidates = ChordCandidate::from_notes(&[Note::C3, Note::E3, Note::Asharp3]);
+        let first = candidates.first().unwrap();
+        assert_eq!(first.chord(), &Chord::Dominant7th(Note::C3));
+        assert_eq!(first.missing(), &[Interval::P5]);
+        assert!(first.added().is_empty());
+    }
+
+    #[test]
+    fn test_from_notes_added() {
+        let candidates = ChordCandidate::from_notes(&[Note::C3, Note::E3, Note::G3, Note::Fsharp4]);
+        let first = candidates.first().unwrap();
+        assert_eq!(first.chord(), &Chord::Major(Note::C3));
+        assert!(first.missing().is_empty());
+        assert_eq!(first.added(), &[PitchClass::FSharp]);
+    }
+
+    #[test]
+    fn test_from_notes_ambiguous() {
+        let candidates = ChordCandidate::from_notes(&[Note::A2, Note::C3, Note::E3, Note::G3]);
+        assert_eq!(candidates[0].chord(), &Chord::Minor7th(Note::A2));
+        assert_eq!(candidates[1].chord(), &Chord::Major6th(Note::C2));
+        assert_eq!(candidates[1].inversion(), 3);
+    }
+
+    #[test]
+    fn test_from_notes_slash() {
+        let candidates =
+            ChordCandidate::from_notes(&Slash(Chord::Major(Note::C3), Note::G2).notes());
+        let first = candidates.first().unwrap();
+        assert_eq!(first.chord(), &Chord::Major(Note::C2));
+        assert_eq!(first.inversion(), 2);
+        assert_eq!(first.bass(), None);
+
+        let notes = Slash(Chord::Major(Note::C3), Note::Fsharp2).notes();
+        let first = ChordCandidate::from_notes(&notes).remove(0);
+        assert_eq!(first.chord(), &Chord::Major(Note::C3));
+        assert_eq!(first.bass(), Some(&Note::Fsharp2));
+        assert_eq!(first.added(), &[PitchClass::FSharp]);
+        assert_eq!(first.notes(), notes);
+        assert_eq!(first.to_string(), "C/F#");
+    }
+
+    #[test]
+    fn test_from_pitch_classes() {
+        let candidates = ChordCandidate::from_pitch_classes(&[
+            PitchClass::G,
+            PitchClass::B,
+            PitchClass::D,
+            PitchClass::F,
+        ]);
+        let first = candidates.first().unwrap();
+        assert_eq!(first.chord(), &Chord::Dominant7th(Note::G4));
+        assert_eq!(first.inversion(), 0);
+        assert_eq!(ChordCandidate::from_pitch_classes(&[]), vec![]);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        // NOTE: Recognition is the same in every octave, so roots within one octave where the
+        //       chord tones wrap at different places are enough
+        for root in [Note::C3, Note::Dsharp3, Note::Fsharp3, Note::A3] {
+            for constructor in Chord::CONSTRUCTORS {
+                let chord = constructor(root.clone());
+                let candidates = ChordCandidate::from_notes(&chord.notes());
+                assert_eq!(candidates[0].chord(), &chord, "{chord}");
+                assert_eq!(candidates[0].inversion(), 0, "{chord}");
+
+                for inversion in 1..chord.intervals().len() {
+                    let notes = chord.inverted(inversion);
+                    let candidates = ChordCandidate::from_notes(&notes);
+                    // NOTE: Some inversions share notes with other chords (e.g. C6 and Am7/C)
+                    let first = &candidates[0];
+                    assert_eq!(pitch_classes(&first.notes()), pitch_classes(&notes));
+                    assert_eq!(first.notes().first(), notes.first());
+                    assert!(
+                        candidates
+                            .iter()
+                            .any(|candidate| candidate.chord() == &chord
+                                && candidate.inversion() == inversion
+                                && candidate.notes() == notes),
+                        "{chord} inversion {inversion}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_trip_inversion() {
+        let notes = Inversion(Chord::Major7th(Note::C3)).notes();
+        let first = ChordCandidate::from_notes(&notes).remove(0);
+        assert_eq!(first.chord(), &Chord::Major7th(Note::C3));
+        assert_eq!(first.inversion(), 1);
+        assert_eq!(first.notes(), notes);
+
+        let notes = Inversion(Inversion(Chord::Minor9th(Note::D3))).notes();
+        let first = ChordCandidate::from_notes(&notes).remove(0);
+        assert_eq!(first.chord(), &Chord::Minor9th(Note::D3));
+        assert_eq!(first.inversion(), 2);
+        assert_eq!(first.notes(), notes);
+    }
+}
@@ -1,7 +1,31 @@
-use super::Note;
+use std::fmt::Debug;
 
-pub trait ChordLike {
+use super::{inversion, Note};
+
+pub trait ChordLike: Debug {
     fn notes(&self) -> Vec<Note>;
 
     fn root_note(&self) -> Note;
+
+    // NOTE: The notes of the given inversion, for inversions only known at runtime
+    fn inverted(&self, inversion: usize) -> Vec<Note> {
+        (0..inversion).fold(self.notes(), |notes, _| inversion::rotate(notes))
+    }
+}
+
+impl<T: ChordLike + ?Sized> ChordLike for Box<T> {
+    #[inline]
+    fn notes(&self) -> Vec<Note> {
+        (**self).notes()
+    }
+
+    #[inline]
+    fn root_note(&self) -> Note {
+        (**self).root_note()
+    }
+
+    #[inline]
+    fn inverted(&self, inversion: usize) -> Vec<Note> {
+        (**self).inverted(inversion)
+    }
 }
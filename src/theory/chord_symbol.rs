@@ -0,0 +1,194 @@
+use std::{fmt::Display, str::FromStr};
+
+use super::{Chord, ChordLike, Note, ParseError, Slash, Spelled};
+
+// NOTE: A chord as written on a lead sheet, e.g. C, C/E or C/D. A bass that is a chord tone
+//       inverts the chord, any other bass sits below it as in Slash
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChordSymbol {
+    chord: Chord,
+    inversion: usize,
+    bass: Option<Note>,
+}
+
+impl ChordSymbol {
+    #[inline]
+    pub fn new(chord: Chord) -> Self {
+        Self::inverted(chord, 0)
+    }
+
+    #[inline]
+    pub fn inverted(chord: Chord, inversion: usize) -> Self {
+        Self {
+            chord,
+            inversion,
+            bass: None,
+        }
+    }
+
+    pub fn over(chord: Chord, bass: Note) -> Self {
+        let pitch_class = bass.pitch_class();
+        match chord
+            .notes()
+            .iter()
+            .position(|note| note.pitch_class() == pitch_class)
+        {
+            Some(inversion) => Self::inverted(chord, inversion),
+            None => Self {
+                chord,
+                inversion: 0,
+                bass: Some(bass),
+            },
+        }
+    }
+
+    // NOTE: Parses plain and slash chord symbols alike, e.g. Cmaj7, C/G or Am7/F#
+    pub fn parse(s: &str, octave: i8) -> Result<Self, ParseError> {
+        if s.contains('/') {
+            let Slash(chord, bass) = Slash::parse(s, octave)?;
+            Ok(Self::over(chord, bass))
+        } else {
+            Ok(Self::new(Chord::parse(s, octave)?))
+        }
+    }
+
+    #[inline]
+    pub fn chord(&self) -> &Chord {
+        &self.chord
+    }
+
+    #[inline]
+    pub fn inversion(&self) -> usize {
+        self.inversion
+    }
+
+    #[inline]
+    pub fn bass_note(&self) -> Option<Note> {
+        self.notes().first().cloned()
+    }
+}
+
+impl FromStr for ChordSymbol {
+    type Err = ParseError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, Chord::DEFAULT_OCTAVE)
+    }
+}
+
+impl From<Chord> for ChordSymbol {
+    #[inline]
+    fn from(chord: Chord) -> Self {
+        Self::new(chord)
+    }
+}
+
+impl ChordLike for ChordSymbol {
+    #[inline]
+    fn notes(&self) -> Vec<Note> {
+        match &self.bass {
+            Some(bass) => Slash(self.chord.clone(), bass.clone()).notes(),
+            None => self.chord.inverted(self.inversion),
+        }
+    }
+
+    #[inline]
+    fn root_note(&self) -> Note {
+        self.chord.root_note()
+    }
+}
+
+// NOTE: Inversions are written over their bass note, as in slash chords
+impl Display for ChordSymbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.bass_note() {
+            Some(bass) if self.inversion > 0 || self.bass.is_some() => {
+                write!(f, "{}", Slash(self.chord.clone(), bass))
+            }
+            _ => write!(f, "{}", self.chord),
+        }
+    }
+}
+
+impl Display for Spelled<'_, ChordSymbol> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let symbol = self.0;
+        match symbol.bass_note() {
+            Some(bass) if symbol.inversion > 0 || symbol.bass.is_some() => {
+                write!(f, "{}", Spelled(&Slash(symbol.chord.clone(), bass)))
+            }
+            _ => write!(f, "{}", Spelled(&symbol.chord)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theory::ParseErrorKind;
+
+    #[test]
+    fn test_notes() {
+        let chord = ChordSymbol::inverted(Chord::Major7th(Note::C3), 2);
+        assert_eq!(chord.notes(), vec![Note::G3, Note::B3, Note::C4, Note::E4]);
+        assert_eq!(chord.root_note(), Note::C3);
+        assert_eq!(chord.bass_note(), Some(Note::G3));
+        assert_eq!(
+            ChordSymbol::from(Chord::Minor(Note::A3)).notes(),
+            vec![Note::A3, Note::C4, Note::E4]
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            "C/G".parse(),
+            Ok(ChordSymbol::inverted(Chord::Major(Note::C4), 2))
+        );
+        assert_eq!(
+            "Cmaj7".parse(),
+            Ok(ChordSymbol::new(Chord::Major7th(Note::C4)))
+        );
+        assert_eq!(
+            ChordSymbol::parse("Am7/G", 2).unwrap().notes(),
+            vec![Note::G3, Note::A3, Note::C4, Note::E4]
+        );
+
+        let chord = ChordSymbol::parse("C/D", 3).unwrap();
+        assert_eq!(chord.inversion(), 0);
+        assert_eq!(chord.notes(), vec![Note::D2, Note::C3, Note::E3, Note::G3]);
+        assert_eq!(chord.root_note(), Note::C3);
+
+        assert_eq!(
+            "C/H".parse::<ChordSymbol>().unwrap_err().kind(),
+            &ParseErrorKind::InvalidBass
+        );
+        assert_eq!(
+            "Cx".parse::<ChordSymbol>().unwrap_err().kind(),
+            &ParseErrorKind::InvalidQuality
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(ChordSymbol::parse("C/D", 3).unwrap().to_string(), "C/D");
+        assert_eq!("Am7/G".parse::<ChordSymbol>().unwrap().to_string(), "Am7/G");
+
+        let chord: ChordSymbol = "Bbm7/Ab".parse().unwrap();
+        assert_eq!(chord.to_string(), "A#m7/G#");
+        assert_eq!(Spelled(&chord).to_string(), "Bbm7/Ab");
+        assert_eq!(
+            ChordSymbol::new(Chord::Minor7th(Note::A3)).to_string(),
+            "Am7"
+        );
+        assert_eq!(
+            ChordSymbol::inverted(Chord::Major(Note::C3), 1).to_string(),
+            "C/E"
+        );
+        assert_eq!(
+            ChordSymbol::inverted(Chord::Dominant7th(Note::G3), 3).to_string(),
+            "G7/F"
+        );
+    }
+}
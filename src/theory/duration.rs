@@ -1,3 +1,5 @@
+use super::NoteValue;
+
 #[allow(dead_code)]
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Duration {
@@ -10,6 +12,18 @@ pub enum Duration {
 }
 
 impl Duration {
+    pub fn from_denominator(denominator: u8) -> Option<Duration> {
+        match denominator {
+            32 => Some(Duration::ThirtySecond),
+            16 => Some(Duration::Sixteenth),
+            8 => Some(Duration::Eighth),
+            4 => Some(Duration::Quarter),
+            2 => Some(Duration::Half),
+            1 => Some(Duration::Whole),
+            _ => None,
+        }
+    }
+
     pub fn beats_per_measure(&self) -> usize {
         match self {
             Duration::ThirtySecond => 32,
@@ -31,4 +45,25 @@ impl Duration {
             Duration::Whole => 1.0,
         }
     }
+
+    #[inline]
+    pub fn note_value(&self) -> NoteValue {
+        NoteValue::new(1, self.beats_per_measure() as u64)
+            .expect("should have non-zero denominator")
+    }
+
+    #[inline]
+    pub fn dotted(&self) -> NoteValue {
+        self.note_value().dotted()
+    }
+
+    #[inline]
+    pub fn double_dotted(&self) -> NoteValue {
+        self.note_value().double_dotted()
+    }
+
+    #[inline]
+    pub fn tuplet(&self, count: u64, space: u64) -> Option<NoteValue> {
+        self.note_value().tuplet(count, space)
+    }
 }
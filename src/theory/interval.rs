@@ -1,6 +1,9 @@
-use std::fmt::Display;
+use std::{
+    fmt::Display,
+    ops::{Add, Sub},
+};
 
-use super::Quality;
+use super::{Note, Quality, SpelledNote};
 
 #[allow(dead_code)]
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -32,12 +35,179 @@ pub enum Interval {
     D8,
     P8,
     A8,
+    D9,
     Min9,
     Maj9,
+    A9,
+    D10,
+    Min10,
+    Maj10,
+    A10,
+    D11,
+    P11,
+    A11,
+    D12,
+    P12,
+    A12,
+    D13,
+    Min13,
+    Maj13,
+    A13,
+    D14,
+    Min14,
+    Maj14,
+    A14,
+    D15,
+    P15,
+    A15,
 }
 
 #[allow(dead_code)]
 impl Interval {
+    const SEMITONES: u8 = 12;
+    const DEGREES: u8 = 7;
+
+    pub const ALL: [Interval; 52] = [
+        Interval::P1,
+        Interval::A1,
+        Interval::D2,
+        Interval::Min2,
+        Interval::Maj2,
+        Interval::A2,
+        Interval::D3,
+        Interval::Min3,
+        Interval::Maj3,
+        Interval::A3,
+        Interval::D4,
+        Interval::P4,
+        Interval::A4,
+        Interval::D5,
+        Interval::P5,
+        Interval::A5,
+        Interval::D6,
+        Interval::Min6,
+        Interval::Maj6,
+        Interval::A6,
+        Interval::D7,
+        Interval::Min7,
+        Interval::Maj7,
+        Interval::A7,
+        Interval::D8,
+        Interval::P8,
+        Interval::A8,
+        Interval::D9,
+        Interval::Min9,
+        Interval::Maj9,
+        Interval::A9,
+        Interval::D10,
+        Interval::Min10,
+        Interval::Maj10,
+        Interval::A10,
+        Interval::D11,
+        Interval::P11,
+        Interval::A11,
+        Interval::D12,
+        Interval::P12,
+        Interval::A12,
+        Interval::D13,
+        Interval::Min13,
+        Interval::Maj13,
+        Interval::A13,
+        Interval::D14,
+        Interval::Min14,
+        Interval::Maj14,
+        Interval::A14,
+        Interval::D15,
+        Interval::P15,
+        Interval::A15,
+    ];
+
+    pub fn new(quality: Quality, degree: u8) -> Option<Interval> {
+        Self::ALL
+            .into_iter()
+            .find(|interval| interval.quality() == quality && interval.degree() == degree)
+    }
+
+    // NOTE: Picks the conventional spelling (e.g. 6 semitones is d5, not A4)
+    pub fn from_semitones(semitones: u8) -> Option<Interval> {
+        let degree = match semitones % Self::SEMITONES {
+            0 => 1,
+            1 | 2 => 2,
+            3 | 4 => 3,
+            5 => 4,
+            6 | 7 => 5,
+            8 | 9 => 6,
+            _ => 7,
+        } + Self::DEGREES * (semitones / Self::SEMITONES);
+        Self::from_degree_and_semitones(degree, semitones)
+    }
+
+    pub fn from_degree_and_semitones(degree: u8, semitones: u8) -> Option<Interval> {
+        Self::ALL
+            .into_iter()
+            .find(|interval| interval.degree() == degree && interval.semitones() == semitones)
+    }
+
+    pub fn between(from: &Note, to: &Note) -> Option<Interval> {
+        Self::from_semitones(from.note_number().abs_diff(to.note_number()))
+    }
+
+    // NOTE: The degree is given by the written letters, e.g. C4 to G#4 is A5 but C4 to Ab4 is m6
+    pub fn spelled_between(from: &SpelledNote, to: &SpelledNote) -> Option<Interval> {
+        let steps = to.letter_number() - from.letter_number();
+        let semitones = to.note_number() - from.note_number();
+        // NOTE: Letters and pitches moving in opposite directions have no interval, e.g. B#3 to Cb4
+        if steps.signum() * semitones.signum() < 0 {
+            return None;
+        }
+        let degree = u8::try_from(steps.unsigned_abs() + 1).ok()?;
+        let semitones = u8::try_from(semitones.unsigned_abs()).ok()?;
+        Self::from_degree_and_semitones(degree, semitones)
+    }
+
+    #[inline]
+    pub fn is_compound(&self) -> bool {
+        self.degree() > Self::DEGREES + 1
+    }
+
+    pub fn simple(&self) -> Interval {
+        if self.is_compound() {
+            Self::from_degree_and_semitones(
+                self.degree() - Self::DEGREES,
+                self.semitones() - Self::SEMITONES,
+            )
+            .expect("should be simple interval")
+        } else {
+            self.clone()
+        }
+    }
+
+    pub fn invert(&self) -> Interval {
+        let simple = match self.simple() {
+            Interval::A8 => Interval::A1,
+            simple => simple,
+        };
+        Self::from_degree_and_semitones(
+            Self::DEGREES + 2 - simple.degree(),
+            Self::SEMITONES - simple.semitones(),
+        )
+        .expect("should be inverted interval")
+    }
+
+    pub fn checked_add(&self, rhs: &Interval) -> Option<Interval> {
+        Self::from_degree_and_semitones(
+            self.degree() + rhs.degree() - 1,
+            self.semitones() + rhs.semitones(),
+        )
+    }
+
+    pub fn checked_sub(&self, rhs: &Interval) -> Option<Interval> {
+        Self::from_degree_and_semitones(
+            self.degree().checked_sub(rhs.degree())? + 1,
+            self.semitones().checked_sub(rhs.semitones())?,
+        )
+    }
+
     pub fn quality(&self) -> Quality {
         match self {
             Interval::P1 => Quality::P,
@@ -67,8 +237,31 @@ impl Interval {
             Interval::D8 => Quality::D,
             Interval::P8 => Quality::P,
             Interval::A8 => Quality::A,
+            Interval::D9 => Quality::D,
             Interval::Min9 => Quality::Min,
             Interval::Maj9 => Quality::Maj,
+            Interval::A9 => Quality::A,
+            Interval::D10 => Quality::D,
+            Interval::Min10 => Quality::Min,
+            Interval::Maj10 => Quality::Maj,
+            Interval::A10 => Quality::A,
+            Interval::D11 => Quality::D,
+            Interval::P11 => Quality::P,
+            Interval::A11 => Quality::A,
+            Interval::D12 => Quality::D,
+            Interval::P12 => Quality::P,
+            Interval::A12 => Quality::A,
+            Interval::D13 => Quality::D,
+            Interval::Min13 => Quality::Min,
+            Interval::Maj13 => Quality::Maj,
+            Interval::A13 => Quality::A,
+            Interval::D14 => Quality::D,
+            Interval::Min14 => Quality::Min,
+            Interval::Maj14 => Quality::Maj,
+            Interval::A14 => Quality::A,
+            Interval::D15 => Quality::D,
+            Interval::P15 => Quality::P,
+            Interval::A15 => Quality::A,
         }
     }
 
@@ -101,8 +294,31 @@ impl Interval {
             Interval::D8 => 8,
             Interval::P8 => 8,
             Interval::A8 => 8,
+            Interval::D9 => 9,
             Interval::Min9 => 9,
             Interval::Maj9 => 9,
+            Interval::A9 => 9,
+            Interval::D10 => 10,
+            Interval::Min10 => 10,
+            Interval::Maj10 => 10,
+            Interval::A10 => 10,
+            Interval::D11 => 11,
+            Interval::P11 => 11,
+            Interval::A11 => 11,
+            Interval::D12 => 12,
+            Interval::P12 => 12,
+            Interval::A12 => 12,
+            Interval::D13 => 13,
+            Interval::Min13 => 13,
+            Interval::Maj13 => 13,
+            Interval::A13 => 13,
+            Interval::D14 => 14,
+            Interval::Min14 => 14,
+            Interval::Maj14 => 14,
+            Interval::A14 => 14,
+            Interval::D15 => 15,
+            Interval::P15 => 15,
+            Interval::A15 => 15,
         }
     }
 
@@ -135,8 +351,31 @@ impl Interval {
             Interval::D8 => 11,
             Interval::P8 => 12,
             Interval::A8 => 13,
+            Interval::D9 => 12,
             Interval::Min9 => 13,
             Interval::Maj9 => 14,
+            Interval::A9 => 15,
+            Interval::D10 => 14,
+            Interval::Min10 => 15,
+            Interval::Maj10 => 16,
+            Interval::A10 => 17,
+            Interval::D11 => 16,
+            Interval::P11 => 17,
+            Interval::A11 => 18,
+            Interval::D12 => 18,
+            Interval::P12 => 19,
+            Interval::A12 => 20,
+            Interval::D13 => 19,
+            Interval::Min13 => 20,
+            Interval::Maj13 => 21,
+            Interval::A13 => 22,
+            Interval::D14 => 21,
+            Interval::Min14 => 22,
+            Interval::Maj14 => 23,
+            Interval::A14 => 24,
+            Interval::D15 => 23,
+            Interval::P15 => 24,
+            Interval::A15 => 25,
         }
     }
 }
@@ -149,6 +388,54 @@ impl Display for Interval {
     }
 }
 
+impl Add for Interval {
+    type Output = Option<Interval>;
+
+    fn add(self, rhs: Interval) -> Self::Output {
+        self.checked_add(&rhs)
+    }
+}
+
+impl Sub for Interval {
+    type Output = Option<Interval>;
+
+    fn sub(self, rhs: Interval) -> Self::Output {
+        self.checked_sub(&rhs)
+    }
+}
+
+impl Add<Interval> for Note {
+    type Output = Option<Note>;
+
+    fn add(self, rhs: Interval) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+impl Add<&Interval> for &Note {
+    type Output = Option<Note>;
+
+    fn add(self, rhs: &Interval) -> Self::Output {
+        self.checked_transpose(rhs.semitones() as i8)
+    }
+}
+
+impl Sub<Interval> for Note {
+    type Output = Option<Note>;
+
+    fn sub(self, rhs: Interval) -> Self::Output {
+        &self - &rhs
+    }
+}
+
+impl Sub<&Interval> for &Note {
+    type Output = Option<Note>;
+
+    fn sub(self, rhs: &Interval) -> Self::Output {
+        self.checked_transpose(-(rhs.semitones() as i8))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -160,5 +447,118 @@ mod tests {
         assert_eq!(Interval::Maj3.to_string(), "M3");
         assert_eq!(Interval::A4.to_string(), "A4");
         assert_eq!(Interval::D5.to_string(), "d5");
+        assert_eq!(Interval::A9.to_string(), "A9");
+        assert_eq!(Interval::P11.to_string(), "P11");
+        assert_eq!(Interval::Maj13.to_string(), "M13");
+        assert_eq!(Interval::P15.to_string(), "P15");
+    }
+
+    #[test]
+    fn test_new() {
+        assert_eq!(Interval::new(Quality::Maj, 3), Some(Interval::Maj3));
+        assert_eq!(Interval::new(Quality::P, 12), Some(Interval::P12));
+        assert_eq!(Interval::new(Quality::Maj, 5), None);
+        assert_eq!(Interval::new(Quality::P, 16), None);
+    }
+
+    #[test]
+    fn test_from_semitones() {
+        assert_eq!(Interval::from_semitones(0), Some(Interval::P1));
+        assert_eq!(Interval::from_semitones(6), Some(Interval::D5));
+        assert_eq!(Interval::from_semitones(12), Some(Interval::P8));
+        assert_eq!(Interval::from_semitones(16), Some(Interval::Maj10));
+        assert_eq!(Interval::from_semitones(24), Some(Interval::P15));
+        assert_eq!(Interval::from_semitones(25), None);
+        for semitones in 0..=24 {
+            assert_eq!(
+                Interval::from_semitones(semitones).map(|interval| interval.semitones()),
+                Some(semitones)
+            );
+        }
+    }
+
+    #[test]
+    fn test_between() {
+        assert_eq!(
+            Interval::between(&Note::E3, &Note::C4),
+            Some(Interval::Min6)
+        );
+        assert_eq!(
+            Interval::between(&Note::C4, &Note::E3),
+            Some(Interval::Min6)
+        );
+        assert_eq!(Interval::between(&Note::C4, &Note::C4), Some(Interval::P1));
+        assert_eq!(
+            Interval::between(&Note::C3, &Note::D4),
+            Some(Interval::Maj9)
+        );
+        assert_eq!(Interval::between(&Note::C3, &Note::C6), None);
+
+        let spelled_between = |from: &str, to: &str| {
+            Interval::spelled_between(&from.parse().unwrap(), &to.parse().unwrap())
+        };
+        assert_eq!(spelled_between("C4", "G#4"), Some(Interval::A5));
+        assert_eq!(spelled_between("C4", "Ab4"), Some(Interval::Min6));
+        assert_eq!(spelled_between("Ab4", "C4"), Some(Interval::Min6));
+        assert_eq!(spelled_between("E3", "C4"), Some(Interval::Min6));
+        assert_eq!(spelled_between("B3", "Cb4"), Some(Interval::D2));
+        assert_eq!(spelled_between("C3", "D#4"), Some(Interval::A9));
+        assert_eq!(spelled_between("B#3", "Cb4"), None);
+    }
+
+    #[test]
+    fn test_simple() {
+        assert!(!Interval::P8.is_compound());
+        assert!(Interval::Min9.is_compound());
+        assert_eq!(Interval::Min9.simple(), Interval::Min2);
+        assert_eq!(Interval::P11.simple(), Interval::P4);
+        assert_eq!(Interval::A15.simple(), Interval::A8);
+        assert_eq!(Interval::Maj3.simple(), Interval::Maj3);
+    }
+
+    #[test]
+    fn test_invert() {
+        assert_eq!(Interval::P1.invert(), Interval::P8);
+        assert_eq!(Interval::P8.invert(), Interval::P1);
+        assert_eq!(Interval::Maj3.invert(), Interval::Min6);
+        assert_eq!(Interval::Min7.invert(), Interval::Maj2);
+        assert_eq!(Interval::A4.invert(), Interval::D5);
+        assert_eq!(Interval::D2.invert(), Interval::A7);
+        assert_eq!(Interval::A8.invert(), Interval::D8);
+        assert_eq!(Interval::Maj10.invert(), Interval::Min6);
+        for interval in Interval::ALL {
+            if interval.degree() < 8 {
+                assert_eq!(interval.invert().invert(), interval);
+            }
+        }
+    }
+
+    #[test]
+    fn test_add() {
+        assert_eq!(Interval::Maj3 + Interval::Min3, Some(Interval::P5));
+        assert_eq!(Interval::P5 + Interval::P4, Some(Interval::P8));
+        assert_eq!(Interval::P8 + Interval::Maj3, Some(Interval::Maj10));
+        assert_eq!(Interval::P8 + Interval::P8, Some(Interval::P15));
+        assert_eq!(Interval::A4 + Interval::A4, Some(Interval::A7));
+        assert_eq!(Interval::A5 + Interval::A5, None);
+        assert_eq!(Interval::P15 + Interval::Min2, None);
+    }
+
+    #[test]
+    fn test_sub() {
+        assert_eq!(Interval::P5 - Interval::Maj3, Some(Interval::Min3));
+        assert_eq!(Interval::Maj3 - Interval::Min3, Some(Interval::A1));
+        assert_eq!(Interval::Maj13 - Interval::P8, Some(Interval::Maj6));
+        assert_eq!(Interval::Maj3 - Interval::P5, None);
+    }
+
+    #[test]
+    fn test_note_add_sub() {
+        assert_eq!(Note::C4 + Interval::Maj3, Some(Note::E4));
+        assert_eq!(&Note::C4 + &Interval::P15, Some(Note::C6));
+        assert_eq!(Note::E4 - Interval::Min6, Some(Note::Gsharp3));
+        assert_eq!(&Note::C4 - &Interval::P8, Some(Note::C3));
+        assert_eq!(Note::G9 + Interval::Min2, None);
+        assert_eq!(Note::Cminus1 - Interval::Min2, None);
     }
 }
@@ -1,23 +1,37 @@
 use std::collections::VecDeque;
 
-use super::{ChordLike, Note};
+use super::{ChordLike, Interval, Note};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Inversion<T>(pub T);
 
 impl<T: ChordLike> ChordLike for Inversion<T> {
+    #[inline]
     fn notes(&self) -> Vec<Note> {
-        let mut notes: VecDeque<Note> = self.0.notes().into();
-        if let Some(first) = notes.pop_front().and_then(|note| note.octave_up()) {
-            notes.push_back(first);
-        }
-        notes.into()
+        self.0.inverted(1)
     }
 
     #[inline]
     fn root_note(&self) -> Note {
         self.0.root_note()
     }
+
+    // NOTE: Nested inversions add up, so the inner chord decides what an inversion moves
+    #[inline]
+    fn inverted(&self, inversion: usize) -> Vec<Note> {
+        self.0.inverted(inversion + 1)
+    }
+}
+
+// NOTE: Moves the lowest note an octave up, and leaves the chord as is rather than dropping a note
+//       that cannot go an octave up
+pub(crate) fn rotate(notes: Vec<Note>) -> Vec<Note> {
+    let mut notes: VecDeque<Note> = notes.into();
+    if let Some(first) = notes.front().and_then(|note| note.clone() + Interval::P8) {
+        notes.pop_front();
+        notes.push_back(first);
+    }
+    notes.into()
 }
 
 #[cfg(test)]
@@ -82,4 +96,20 @@ mod tests {
             vec![Note::C4, Note::E4, Note::G4, Note::B4, Note::D5]
         );
     }
+
+    #[test]
+    fn test_inverted() {
+        assert_eq!(
+            Chord::Major7th(Note::C3).inverted(2),
+            Inversion(Inversion(Chord::Major7th(Note::C3))).notes()
+        );
+        assert_eq!(
+            Chord::Major(Note::C3).inverted(0),
+            vec![Note::C3, Note::E3, Note::G3]
+        );
+        assert_eq!(
+            Inversion(Chord::Major(Note::C9)).notes(),
+            vec![Note::C9, Note::E9, Note::G9]
+        );
+    }
 }
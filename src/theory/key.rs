@@ -0,0 +1,679 @@
+use std::{fmt::Display, str::FromStr};
+
+use super::{
+    Chord, ChordLike, ChordSymbol, Note, ParseError, ParseErrorKind, PitchClass, Scale,
+    SpelledNote, SpelledPitchClass,
+};
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Extension {
+    Triad,
+    Seventh,
+    Ninth,
+}
+
+impl Extension {
+    fn len(&self) -> usize {
+        match self {
+            Extension::Triad => 3,
+            Extension::Seventh => 4,
+            Extension::Ninth => 5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Key {
+    tonic: PitchClass,
+    mode: Scale,
+}
+
+impl Key {
+    const SEMITONES: u8 = 12;
+
+    pub fn new(tonic: PitchClass, mode: Scale) -> Self {
+        Self { tonic, mode }
+    }
+
+    #[inline]
+    pub fn tonic(&self) -> &PitchClass {
+        &self.tonic
+    }
+
+    #[inline]
+    pub fn mode(&self) -> &Scale {
+        &self.mode
+    }
+
+    #[inline]
+    pub fn contains(&self, note: &Note) -> bool {
+        self.mode.contains(&self.tonic, note)
+    }
+
+    #[inline]
+    pub fn quantize(&self, note: &Note) -> Option<Note> {
+        self.mode.quantize(&self.tonic, note)
+    }
+
+    pub fn chord(&self, degree: usize, extension: Extension, octave: i8) -> Option<Chord> {
+        let tonic = Note::from_pitch_class(&self.tonic, octave)?;
+        let len = self.mode.intervals().len();
+        if degree == 0 || degree > len {
+            return None;
+        }
+
+        let root = self.scale_semitones(degree - 1);
+        let semitones: Vec<u8> = (0..extension.len())
+            .map(|i| self.scale_semitones(degree - 1 + i * 2) - root)
+            .collect();
+        let root = tonic.checked_transpose(root as i8)?;
+
+        match semitones.as_slice() {
+            [0, 4, 7] => Some(Chord::Major(root)),
+            [0, 3, 7] => Some(Chord::Minor(root)),
+            [0, 3, 6] => Some(Chord::Diminished(root)),
+            [0, 4, 8] => Some(Chord::Augmented(root)),
+            [0, 4, 7, 11] => Some(Chord::Major7th(root)),
+            [0, 3, 7, 10] => Some(Chord::Minor7th(root)),
+            [0, 4, 7, 10] => Some(Chord::Dominant7th(root)),
+            [0, 3, 6, 10] => Some(Chord::HalfDiminished7th(root)),
+            [0, 3, 6, 9] => Some(Chord::Diminished7th(root)),
+            [0, 3, 7, 11] => Some(Chord::MinorMajor7th(root)),
+            [0, 4, 8, 11] => Some(Chord::AugmentedMajor7th(root)),
+            [0, 4, 7, 11, 14] => Some(Chord::Major9th(root)),
+            [0, 3, 7, 10, 14] => Some(Chord::Minor9th(root)),
+            [0, 4, 7, 10, 14] => Some(Chord::Dominant9th(root)),
+            _ => None,
+        }
+    }
+
+    // NOTE: One entry per degree, None where the stacked thirds are no known chord
+    pub fn chords(&self, extension: Extension, octave: i8) -> Vec<Option<Chord>> {
+        (1..=self.mode.intervals().len())
+            .map(|degree| self.chord(degree, extension.clone(), octave))
+            .collect()
+    }
+
+    pub fn numeral(&self, numeral: &RomanNumeral, octave: i8) -> Result<ChordSymbol, ParseError> {
+        let tonic = Note::from_pitch_class(&self.tonic, octave).ok_or_else(|| {
+            ParseError::new(
+                ParseErrorKind::OutOfRange,
+                format!("octave out of range: {octave}"),
+            )
+        })?;
+        self.numeral_from_tonic(numeral, &tonic)
+    }
+
+    pub fn progression(&self, s: &str, octave: i8) -> Result<Vec<ChordSymbol>, ParseError> {
+        s.split(|c: char| c.is_whitespace() || matches!(c, ',' | '-' | '–' | '—'))
+            .filter(|token| !token.is_empty())
+            .map(|token| self.numeral(&token.parse()?, octave))
+            .collect()
+    }
+
+    fn numeral_from_tonic(
+        &self,
+        numeral: &RomanNumeral,
+        tonic: &Note,
+    ) -> Result<ChordSymbol, ParseError> {
+        if let Some(target) = &numeral.secondary {
+            let target_root = self.numeral_root(target, tonic)?;
+            let mode = if target.is_upper() {
+                Scale::Major
+            } else {
+                Scale::NaturalMinor
+            };
+            let key = Key::new(target_root.pitch_class(), mode);
+            let primary = RomanNumeral {
+                secondary: None,
+                ..numeral.clone()
+            };
+            return key.numeral_from_tonic(&primary, &target_root);
+        }
+
+        let root = self.numeral_root(numeral, tonic)?;
+        let chord = numeral.chord(root)?;
+        Ok(ChordSymbol::inverted(chord, numeral.inversion))
+    }
+
+    fn numeral_root(&self, numeral: &RomanNumeral, tonic: &Note) -> Result<Note, ParseError> {
+        let interval = self.mode.degree(numeral.degree).ok_or_else(|| {
+            ParseError::new(
+                ParseErrorKind::OutOfRange,
+                format!("degree out of range: {}", numeral.degree),
+            )
+        })?;
+        tonic
+            .checked_transpose(interval.semitones() as i8 + numeral.accidental)
+            .ok_or_else(|| {
+                ParseError::new(
+                    ParseErrorKind::OutOfRange,
+                    format!("root out of range: {numeral}"),
+                )
+            })
+    }
+
+    pub fn spelled_tonic(&self) -> SpelledPitchClass {
+        let intervals = self.mode.intervals();
+        SpelledPitchClass::simplest(&self.tonic, |tonic| {
+            intervals
+                .iter()
+                .map(|interval| tonic.transpose(interval))
+                .collect()
+        })
+    }
+
+    pub fn spelled_scale(&self) -> Vec<SpelledPitchClass> {
+        let tonic = self.spelled_tonic();
+        self.mode
+            .intervals()
+            .iter()
+            .filter_map(|interval| tonic.transpose(interval))
+            .collect()
+    }
+
+    // NOTE: Chromatic notes follow the key's direction, e.g. flats in F major
+    pub fn spell(&self, note: &Note) -> SpelledNote {
+        let pitch_class = note.pitch_class();
+        let scale = self.spelled_scale();
+        let spelled = scale
+            .iter()
+            .find(|spelled| spelled.pitch_class() == pitch_class)
+            .cloned()
+            .unwrap_or_else(|| {
+                if scale
+                    .iter()
+                    .any(|spelled| spelled.accidental().offset() < 0)
+                {
+                    SpelledPitchClass::flat(&pitch_class)
+                } else {
+                    SpelledPitchClass::sharp(&pitch_class)
+                }
+            });
+        SpelledNote::from_note(note, &spelled).expect("should be spelled with its own pitch class")
+    }
+
+    pub fn spell_chord(&self, chord: &Chord) -> Vec<SpelledNote> {
+        let root = self.spell(&chord.root_note());
+        chord
+            .intervals()
+            .iter()
+            .filter_map(|interval| root.transpose(interval))
+            .collect()
+    }
+
+    fn scale_semitones(&self, index: usize) -> u8 {
+        let intervals = self.mode.intervals();
+        let octaves = (index / intervals.len()) as u8;
+        intervals[index % intervals.len()].semitones() + octaves * Self::SEMITONES
+    }
+}
+
+impl Display for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.spelled_tonic(), self.mode)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum NumeralQuality {
+    Major,
+    Minor,
+    Diminished,
+    HalfDiminished,
+    Augmented,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RomanNumeral {
+    accidental: i8,
+    degree: usize,
+    quality: NumeralQuality,
+    major_seventh: bool,
+    extension: Extension,
+    inversion: usize,
+    secondary: Option<Box<RomanNumeral>>,
+    source: String,
+}
+
+impl RomanNumeral {
+    #[inline]
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    #[inline]
+    pub fn inversion(&self) -> usize {
+        self.inversion
+    }
+
+    #[inline]
+    pub fn extension(&self) -> &Extension {
+        &self.extension
+    }
+
+    #[inline]
+    pub fn secondary(&self) -> Option<&RomanNumeral> {
+        self.secondary.as_deref()
+    }
+
+    fn is_upper(&self) -> bool {
+        matches!(
+            self.quality,
+            NumeralQuality::Major | NumeralQuality::Augmented
+        )
+    }
+
+    fn chord(&self, root: Note) -> Result<Chord, ParseError> {
+        let chord = match (&self.extension, &self.quality, self.major_seventh) {
+            (Extension::Triad, NumeralQuality::Major, _) => Chord::Major(root),
+            (Extension::Triad, NumeralQuality::Minor, _) => Chord::Minor(root),
+            (Extension::Triad, NumeralQuality::Diminished, _) => Chord::Diminished(root),
+            (Extension::Triad, NumeralQuality::Augmented, _) => Chord::Augmented(root),
+            (Extension::Seventh, NumeralQuality::Major, true) => Chord::Major7th(root),
+            (Extension::Seventh, NumeralQuality::Major, false) => Chord::Dominant7th(root),
+            (Extension::Seventh, NumeralQuality::Minor, true) => Chord::MinorMajor7th(root),
+            (Extension::Seventh, NumeralQuality::Minor, false) => Chord::Minor7th(root),
+            (Extension::Seventh, NumeralQuality::Diminished, _) => Chord::Diminished7th(root),
+            (_, NumeralQuality::HalfDiminished, _) => Chord::HalfDiminished7th(root),
+            (Extension::Seventh, NumeralQuality::Augmented, true) => Chord::AugmentedMajor7th(root),
+            (Extension::Ninth, NumeralQuality::Major, true) => Chord::Major9th(root),
+            (Extension::Ninth, NumeralQuality::Major, false) => Chord::Dominant9th(root),
+            (Extension::Ninth, NumeralQuality::Minor, false) => Chord::Minor9th(root),
+            _ => {
+                return Err(ParseError::new(
+                    ParseErrorKind::Unsupported,
+                    format!("unsupported numeral: {self}"),
+                ))
+            }
+        };
+        Ok(chord)
+    }
+
+    fn parse_degree(s: &str) -> Option<(usize, bool, &str)> {
+        let end = s
+            .find(|c: char| !matches!(c, 'I' | 'V' | 'i' | 'v'))
+            .unwrap_or(s.len());
+        let (numeral, rest) = s.split_at(end);
+        let upper = numeral.chars().all(|c| c.is_ascii_uppercase());
+        let lower = numeral.chars().all(|c| c.is_ascii_lowercase());
+        if !(upper || lower) {
+            return None;
+        }
+
+        let degree = match numeral.to_ascii_uppercase().as_str() {
+            "I" => 1,
+            "II" => 2,
+            "III" => 3,
+            "IV" => 4,
+            "V" => 5,
+            "VI" => 6,
+            "VII" => 7,
+            _ => return None,
+        };
+        Some((degree, upper, rest))
+    }
+}
+
+impl FromStr for RomanNumeral {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || {
+            ParseError::new(
+                ParseErrorKind::InvalidNumeral,
+                format!("invalid roman numeral: {s}"),
+            )
+        };
+
+        let (s_primary, secondary) = match s.split_once('/') {
+            Some((primary, secondary)) => (primary, Some(Box::new(secondary.parse()?))),
+            None => (s, None),
+        };
+
+        let (accidental, rest) = if let Some(rest) = s_primary.strip_prefix('b') {
+            (-1, rest)
+        } else if let Some(rest) = s_primary.strip_prefix('#') {
+            (1, rest)
+        } else {
+            (0, s_primary)
+        };
+
+        let (degree, upper, rest) = Self::parse_degree(rest).ok_or_else(error)?;
+
+        let (quality, rest) = if let Some(rest) = rest
+            .strip_prefix('°')
+            .or_else(|| rest.strip_prefix("dim"))
+            .or_else(|| rest.strip_prefix('o'))
+        {
+            (NumeralQuality::Diminished, rest)
+        } else if let Some(rest) = rest.strip_prefix('ø') {
+            (NumeralQuality::HalfDiminished, rest)
+        } else if let Some(rest) = rest.strip_prefix('+').or_else(|| rest.strip_prefix("aug")) {
+            (NumeralQuality::Augmented, rest)
+        } else if upper {
+            (NumeralQuality::Major, rest)
+        } else {
+            (NumeralQuality::Minor, rest)
+        };
+
+        let (major_seventh, rest) =
+            match rest.strip_prefix("maj").or_else(|| rest.strip_prefix('M')) {
+                Some(rest) => (true, rest),
+                None => (false, rest),
+            };
+
+        let (extension, inversion) = match rest {
+            "" => (Extension::Triad, 0),
+            "6" => (Extension::Triad, 1),
+            "64" => (Extension::Triad, 2),
+            "7" => (Extension::Seventh, 0),
+            "65" => (Extension::Seventh, 1),
+            "43" => (Extension::Seventh, 2),
+            "42" | "2" => (Extension::Seventh, 3),
+            "9" => (Extension::Ninth, 0),
+            _ => return Err(error()),
+        };
+
+        Ok(Self {
+            accidental,
+            degree,
+            quality,
+            major_seventh,
+            extension,
+            inversion,
+            secondary,
+            source: s.to_string(),
+        })
+    }
+}
+
+impl Display for RomanNumeral {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notes(key: &Key, s: &str, octave: i8) -> Vec<Vec<Note>> {
+        key.progression(s, octave)
+            .unwrap()
+            .iter()
+            .map(|chord| chord.notes())
+            .collect()
+    }
+
+    fn chord_notes(chords: Vec<Chord>) -> Vec<Vec<Note>> {
+        chords.iter().map(|chord| chord.notes()).collect()
+    }
+
+    #[test]
+    fn test_chord() {
+        let key = Key::new(PitchClass::C, Scale::Major);
+        assert_eq!(
+            key.chord(1, Extension::Triad, 4),
+            Some(Chord::Major(Note::C4))
+        );
+        assert_eq!(
+            key.chord(2, Extension::Triad, 4),
+            Some(Chord::Minor(Note::D4))
+        );
+        assert_eq!(
+            key.chord(7, Extension::Triad, 4),
+            Some(Chord::Diminished(Note::B4))
+        );
+        assert_eq!(
+            key.chord(5, Extension::Seventh, 4),
+            Some(Chord::Dominant7th(Note::G4))
+        );
+        assert_eq!(
+            key.chord(7, Extension::Seventh, 4),
+            Some(Chord::HalfDiminished7th(Note::B4))
+        );
+        assert_eq!(
+            key.chord(1, Extension::Ninth, 4),
+            Some(Chord::Major9th(Note::C4))
+        );
+        assert_eq!(
+            key.chord(2, Extension::Ninth, 4),
+            Some(Chord::Minor9th(Note::D4))
+        );
+        assert_eq!(key.chord(3, Extension::Ninth, 4), None);
+        assert_eq!(key.chord(0, Extension::Triad, 4), None);
+        assert_eq!(key.chord(8, Extension::Triad, 4), None);
+    }
+
+    #[test]
+    fn test_chords() {
+        let key = Key::new(PitchClass::C, Scale::Major);
+        assert_eq!(
+            key.chords(Extension::Triad, 3),
+            vec![
+                Some(Chord::Major(Note::C3)),
+                Some(Chord::Minor(Note::D3)),
+                Some(Chord::Minor(Note::E3)),
+                Some(Chord::Major(Note::F3)),
+                Some(Chord::Major(Note::G3)),
+                Some(Chord::Minor(Note::A3)),
+                Some(Chord::Diminished(Note::B3)),
+            ]
+        );
+
+        let key = Key::new(PitchClass::A, Scale::HarmonicMinor);
+        assert_eq!(
+            key.chords(Extension::Seventh, 3),
+            vec![
+                Some(Chord::MinorMajor7th(Note::A3)),
+                Some(Chord::HalfDiminished7th(Note::B3)),
+                Some(Chord::AugmentedMajor7th(Note::C4)),
+                Some(Chord::Minor7th(Note::D4)),
+                Some(Chord::Dominant7th(Note::E4)),
+                Some(Chord::Major7th(Note::F4)),
+                Some(Chord::Diminished7th(Note::Gsharp4)),
+            ]
+        );
+
+        // NOTE: iii9 and vii9 would need a minor ninth
+        let key = Key::new(PitchClass::C, Scale::Major);
+        assert_eq!(
+            key.chords(Extension::Ninth, 3),
+            vec![
+                Some(Chord::Major9th(Note::C3)),
+                Some(Chord::Minor9th(Note::D3)),
+                None,
+                Some(Chord::Major9th(Note::F3)),
+                Some(Chord::Dominant9th(Note::G3)),
+                Some(Chord::Minor9th(Note::A3)),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_quantize() {
+        let key = Key::new(PitchClass::D, Scale::Major);
+        assert!(key.contains(&Note::Csharp2));
+        assert!(!key.contains(&Note::C2));
+        assert_eq!(key.quantize(&Note::F4), Some(Note::E4));
+        assert_eq!(key.quantize(&Note::Gsharp4), Some(Note::G4));
+    }
+
+    #[test]
+    fn test_progression() {
+        let key = Key::new(PitchClass::C, Scale::Major);
+        assert_eq!(
+            notes(&key, "I–vi–IV–V", 3),
+            chord_notes(vec![
+                Chord::Major(Note::C3),
+                Chord::Minor(Note::A3),
+                Chord::Major(Note::F3),
+                Chord::Major(Note::G3),
+            ])
+        );
+        assert_eq!(
+            notes(&key, "ii7 V7 Imaj7", 3),
+            chord_notes(vec![
+                Chord::Minor7th(Note::D3),
+                Chord::Dominant7th(Note::G3),
+                Chord::Major7th(Note::C3),
+            ])
+        );
+        assert_eq!(
+            notes(&key, "viiø7, vii°7, bVII, III+, V9, IM9, ii9", 3),
+            chord_notes(vec![
+                Chord::HalfDiminished7th(Note::B3),
+                Chord::Diminished7th(Note::B3),
+                Chord::Major(Note::Asharp3),
+                Chord::Augmented(Note::E3),
+                Chord::Dominant9th(Note::G3),
+                Chord::Major9th(Note::C3),
+                Chord::Minor9th(Note::D3),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_progression_modal() {
+        let key = Key::new(PitchClass::C, Scale::Lydian);
+        assert_eq!(
+            notes(&key, "Vmaj9 Imaj9", 1),
+            chord_notes(vec![Chord::Major9th(Note::G1), Chord::Major9th(Note::C1)])
+        );
+    }
+
+    #[test]
+    fn test_progression_secondary() {
+        let key = Key::new(PitchClass::C, Scale::Major);
+        assert_eq!(
+            notes(&key, "V/V V7/ii vii°7/V", 3),
+            chord_notes(vec![
+                Chord::Major(Note::D4),
+                Chord::Dominant7th(Note::A3),
+                Chord::Diminished7th(Note::Fsharp4),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_progression_inversion() {
+        let key = Key::new(PitchClass::C, Scale::Major);
+        let progression = key.progression("I6 V65 I64 V42", 3).unwrap();
+        assert_eq!(
+            progression[0],
+            ChordSymbol::inverted(Chord::Major(Note::C3), 1)
+        );
+        assert_eq!(
+            progression
+                .iter()
+                .map(|chord| chord.to_string())
+                .collect::<Vec<_>>(),
+            vec!["C/E", "G7/B", "C/G", "G7/F"]
+        );
+        assert_eq!(progression[0].notes(), vec![Note::E3, Note::G3, Note::C4]);
+        assert_eq!(
+            progression[1].notes(),
+            vec![Note::B3, Note::D4, Note::F4, Note::G4]
+        );
+        assert_eq!(progression[1].root_note(), Note::G3);
+        assert_eq!(progression[2].notes(), vec![Note::G3, Note::C4, Note::E4]);
+        assert_eq!(
+            progression[3].notes(),
+            vec![Note::F4, Note::G4, Note::B4, Note::D5]
+        );
+    }
+
+    #[test]
+    fn test_progression_errors() {
+        let key = Key::new(PitchClass::C, Scale::Major);
+        assert_eq!(
+            key.progression("I X", 3).unwrap_err(),
+            ParseError::new(ParseErrorKind::InvalidNumeral, "invalid roman numeral: X")
+        );
+        assert_eq!(
+            key.progression("IIII", 3).unwrap_err(),
+            ParseError::new(
+                ParseErrorKind::InvalidNumeral,
+                "invalid roman numeral: IIII"
+            )
+        );
+        assert_eq!(
+            key.progression("V13", 3).unwrap_err(),
+            ParseError::new(ParseErrorKind::InvalidNumeral, "invalid roman numeral: V13")
+        );
+        assert_eq!(
+            key.progression("III+7", 3).unwrap_err(),
+            ParseError::new(ParseErrorKind::Unsupported, "unsupported numeral: III+7")
+        );
+        assert_eq!(
+            Key::new(PitchClass::C, Scale::MajorPentatonic)
+                .progression("VI", 3)
+                .unwrap_err(),
+            ParseError::new(ParseErrorKind::OutOfRange, "degree out of range: 6")
+        );
+    }
+
+    #[test]
+    fn test_spelled_scale() {
+        let spelled = |key: Key| {
+            key.spelled_scale()
+                .iter()
+                .map(|pitch_class| pitch_class.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        assert_eq!(
+            spelled(Key::new(PitchClass::F, Scale::NaturalMinor)),
+            "F G Ab Bb C Db Eb"
+        );
+        assert_eq!(
+            spelled(Key::new(PitchClass::CSharp, Scale::Major)),
+            "Db Eb F Gb Ab Bb C"
+        );
+        assert_eq!(
+            spelled(Key::new(PitchClass::CSharp, Scale::NaturalMinor)),
+            "C# D# E F# G# A B"
+        );
+        assert_eq!(
+            spelled(Key::new(PitchClass::E, Scale::Major)),
+            "E F# G# A B C# D#"
+        );
+        assert_eq!(
+            spelled(Key::new(PitchClass::A, Scale::HarmonicMinor)),
+            "A B C D E F G#"
+        );
+    }
+
+    #[test]
+    fn test_spell() {
+        let key = Key::new(PitchClass::F, Scale::Major);
+        assert_eq!(key.spell(&Note::Asharp3).to_string(), "Bb3");
+        assert_eq!(key.spell(&Note::Gsharp3).to_string(), "Ab3");
+        let key = Key::new(PitchClass::G, Scale::Major);
+        assert_eq!(key.spell(&Note::Fsharp4).to_string(), "F#4");
+        assert_eq!(key.spell(&Note::Csharp4).to_string(), "C#4");
+        let key = Key::new(PitchClass::FSharp, Scale::Major);
+        assert_eq!(key.spell(&Note::B3).to_string(), "B3");
+        assert_eq!(key.spell(&Note::D4).to_string(), "D4");
+        assert_eq!(key.spell(&Note::F4).to_string(), "E#4");
+
+        let key = Key::new(PitchClass::C, Scale::NaturalMinor);
+        let spelled: Vec<String> = key
+            .spell_chord(&Chord::Major(Note::Gsharp3))
+            .iter()
+            .map(|note| note.to_string())
+            .collect();
+        assert_eq!(spelled, vec!["Ab3", "C4", "Eb4"]);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            Key::new(PitchClass::A, Scale::NaturalMinor).to_string(),
+            "A natural minor"
+        );
+    }
+}
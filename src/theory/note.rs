@@ -1,14 +1,26 @@
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
 use wasm_bindgen::prelude::*;
 
 use crate::unit::Frequency;
 
-use super::PitchClass;
+use super::{ParseError, PitchClass, SpelledNote, Tuning};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[wasm_bindgen]
 pub enum Note {
+    Cminus1 = 0,
+    Csharpminus1 = 1,
+    Dminus1 = 2,
+    Dsharpminus1 = 3,
+    Eminus1 = 4,
+    Fminus1 = 5,
+    Fsharpminus1 = 6,
+    Gminus1 = 7,
+    Gsharpminus1 = 8,
+    Aminus1 = 9,
+    Asharpminus1 = 10,
+    Bminus1 = 11,
     C0 = 12,
     Csharp0 = 13,
     D0 = 14,
@@ -81,13 +93,71 @@ pub enum Note {
     A5 = 81,
     Asharp5 = 82,
     B5 = 83,
+    C6 = 84,
+    Csharp6 = 85,
+    D6 = 86,
+    Dsharp6 = 87,
+    E6 = 88,
+    F6 = 89,
+    Fsharp6 = 90,
+    G6 = 91,
+    Gsharp6 = 92,
+    A6 = 93,
+    Asharp6 = 94,
+    B6 = 95,
+    C7 = 96,
+    Csharp7 = 97,
+    D7 = 98,
+    Dsharp7 = 99,
+    E7 = 100,
+    F7 = 101,
+    Fsharp7 = 102,
+    G7 = 103,
+    Gsharp7 = 104,
+    A7 = 105,
+    Asharp7 = 106,
+    B7 = 107,
+    C8 = 108,
+    Csharp8 = 109,
+    D8 = 110,
+    Dsharp8 = 111,
+    E8 = 112,
+    F8 = 113,
+    Fsharp8 = 114,
+    G8 = 115,
+    Gsharp8 = 116,
+    A8 = 117,
+    Asharp8 = 118,
+    B8 = 119,
+    C9 = 120,
+    Csharp9 = 121,
+    D9 = 122,
+    Dsharp9 = 123,
+    E9 = 124,
+    F9 = 125,
+    Fsharp9 = 126,
+    G9 = 127,
 }
 
 impl Note {
     const SEMITONES: u8 = 12;
+    pub const MIN_NOTE_NUMBER: u8 = 0;
+    pub const MAX_NOTE_NUMBER: u8 = 127;
 
     pub fn from_note_number(note_number: u8) -> Option<Self> {
         match note_number {
+            0 => Some(Note::Cminus1),
+            1 => Some(Note::Csharpminus1),
+            2 => Some(Note::Dminus1),
+            3 => Some(Note::Dsharpminus1),
+            4 => Some(Note::Eminus1),
+            5 => Some(Note::Fminus1),
+            6 => Some(Note::Fsharpminus1),
+            7 => Some(Note::Gminus1),
+            8 => Some(Note::Gsharpminus1),
+            9 => Some(Note::Aminus1),
+            10 => Some(Note::Asharpminus1),
+            11 => Some(Note::Bminus1),
             12 => Some(Note::C0),
             13 => Some(Note::Csharp0),
             14 => Some(Note::D0),
@@ -160,10 +230,62 @@ impl Note {
             81 => Some(Note::A5),
             82 => Some(Note::Asharp5),
             83 => Some(Note::B5),
+            84 => Some(Note::C6),
+            85 => Some(Note::Csharp6),
+            86 => Some(Note::D6),
+            87 => Some(Note::Dsharp6),
+            88 => Some(Note::E6),
+            89 => Some(Note::F6),
+            90 => Some(Note::Fsharp6),
+            91 => Some(Note::G6),
+            92 => Some(Note::Gsharp6),
+            93 => Some(Note::A6),
+            94 => Some(Note::Asharp6),
+            95 => Some(Note::B6),
+            96 => Some(Note::C7),
+            97 => Some(Note::Csharp7),
+            98 => Some(Note::D7),
+            99 => Some(Note::Dsharp7),
+            100 => Some(Note::E7),
+            101 => Some(Note::F7),
+            102 => Some(Note::Fsharp7),
+            103 => Some(Note::G7),
+            104 => Some(Note::Gsharp7),
+            105 => Some(Note::A7),
+            106 => Some(Note::Asharp7),
+            107 => Some(Note::B7),
+            108 => Some(Note::C8),
+            109 => Some(Note::Csharp8),
+            110 => Some(Note::D8),
+            111 => Some(Note::Dsharp8),
+            112 => Some(Note::E8),
+            113 => Some(Note::F8),
+            114 => Some(Note::Fsharp8),
+            115 => Some(Note::G8),
+            116 => Some(Note::Gsharp8),
+            117 => Some(Note::A8),
+            118 => Some(Note::Asharp8),
+            119 => Some(Note::B8),
+            120 => Some(Note::C9),
+            121 => Some(Note::Csharp9),
+            122 => Some(Note::D9),
+            123 => Some(Note::Dsharp9),
+            124 => Some(Note::E9),
+            125 => Some(Note::F9),
+            126 => Some(Note::Fsharp9),
+            127 => Some(Note::G9),
             _ => None,
         }
     }
 
+    pub fn from_pitch_class(pitch_class: &PitchClass, octave: i8) -> Option<Self> {
+        let note_number =
+            (octave as i16 + 1) * Self::SEMITONES as i16 + pitch_class.semitones() as i16;
+        u8::try_from(note_number)
+            .ok()
+            .and_then(Self::from_note_number)
+    }
+
     #[inline]
     pub fn octave(&self) -> i8 {
         (self.note_number() / Self::SEMITONES) as i8 - 1
@@ -181,7 +303,31 @@ impl Note {
 
     #[inline]
     pub fn transpose(&self, semitones: i8) -> Option<Note> {
-        Self::from_note_number((self.note_number() as i8 + semitones) as u8)
+        self.checked_transpose(semitones)
+    }
+
+    pub fn checked_transpose(&self, semitones: i8) -> Option<Note> {
+        let note_number = self.note_number() as i16 + semitones as i16;
+        if (Self::MIN_NOTE_NUMBER as i16..=Self::MAX_NOTE_NUMBER as i16).contains(&note_number) {
+            Self::from_note_number(note_number as u8)
+        } else {
+            None
+        }
+    }
+
+    pub fn saturating_transpose(&self, semitones: i8) -> Note {
+        let note_number = (self.note_number() as i16 + semitones as i16)
+            .clamp(Self::MIN_NOTE_NUMBER as i16, Self::MAX_NOTE_NUMBER as i16);
+        Self::from_note_number(note_number as u8).expect("should be in the note range")
+    }
+
+    pub fn wrapping_transpose(&self, semitones: i8) -> Note {
+        let range = Self::MAX_NOTE_NUMBER as i16 - Self::MIN_NOTE_NUMBER as i16 + 1;
+        let note_number = (self.note_number() as i16 - Self::MIN_NOTE_NUMBER as i16
+            + semitones as i16)
+            .rem_euclid(range)
+            + Self::MIN_NOTE_NUMBER as i16;
+        Self::from_note_number(note_number as u8).expect("should be in the note range")
     }
 
     #[inline]
@@ -189,28 +335,19 @@ impl Note {
         self.clone() as u8
     }
 
-    // TODO: support other base frequencies (e.g. 442, 444)
+    #[inline]
     pub fn freq(&self) -> Frequency {
-        let relative_note_number = self.note_number() as i16 - Note::A4.note_number() as i16;
-        (440.0 * (2.0_f32).powf(relative_note_number as f32 / Self::SEMITONES as f32)).into()
+        self.freq_with_tuning(&Tuning::default())
+    }
+
+    #[inline]
+    pub fn freq_with_tuning(&self, tuning: &Tuning) -> Frequency {
+        tuning.freq(self)
     }
 
+    #[inline]
     pub fn pitch_class(&self) -> PitchClass {
-        match self.note_number() % Self::SEMITONES {
-            0 => PitchClass::C,
-            1 => PitchClass::CSharp,
-            2 => PitchClass::D,
-            3 => PitchClass::DSharp,
-            4 => PitchClass::E,
-            5 => PitchClass::F,
-            6 => PitchClass::FSharp,
-            7 => PitchClass::G,
-            8 => PitchClass::GSharp,
-            9 => PitchClass::A,
-            10 => PitchClass::ASharp,
-            11 => PitchClass::B,
-            _ => unreachable!("unsupported pitch class"),
-        }
+        PitchClass::from_semitones(self.note_number())
     }
 }
 
@@ -222,6 +359,29 @@ impl Display for Note {
     }
 }
 
+impl FromStr for Note {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let spelled: SpelledNote = s.parse()?;
+        Ok(spelled.note().expect("should be in the note range"))
+    }
+}
+
+impl TryFrom<&str> for Note {
+    type Error = ParseError;
+
+    #[inline]
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+#[wasm_bindgen(js_name = noteFromName)]
+pub fn note_from_name(name: &str) -> Result<Note, ParseError> {
+    name.parse()
+}
+
 impl From<Note> for Frequency {
     #[inline]
     fn from(value: Note) -> Self {
@@ -232,9 +392,60 @@ impl From<Note> for Frequency {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::theory::{ParseErrorKind, TuningSystem};
+
+    #[test]
+    fn test_from_pitch_class() {
+        assert_eq!(
+            Note::from_pitch_class(&PitchClass::C, -1),
+            Some(Note::Cminus1)
+        );
+        assert_eq!(Note::from_pitch_class(&PitchClass::C, 4), Some(Note::C4));
+        assert_eq!(Note::from_pitch_class(&PitchClass::A, 4), Some(Note::A4));
+        assert_eq!(Note::from_pitch_class(&PitchClass::G, 9), Some(Note::G9));
+        assert_eq!(Note::from_pitch_class(&PitchClass::GSharp, 9), None);
+        assert_eq!(Note::from_pitch_class(&PitchClass::B, -2), None);
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("C4".parse(), Ok(Note::C4));
+        assert_eq!("c#4".parse(), Ok(Note::Csharp4));
+        assert_eq!("Db4".parse(), Ok(Note::Csharp4));
+        assert_eq!("A-1".parse(), Ok(Note::Aminus1));
+        assert_eq!("F♯3".parse(), Ok(Note::Fsharp3));
+        assert_eq!("E♭2".parse(), Ok(Note::Dsharp2));
+        assert_eq!("bb3".parse(), Ok(Note::Asharp3));
+        assert_eq!("G9".parse(), Ok(Note::G9));
+        assert_eq!(Note::try_from("A4"), Ok(Note::A4));
+        assert_eq!(note_from_name("B0"), Ok(Note::B0));
+
+        assert_eq!(
+            "".parse::<Note>().unwrap_err().kind(),
+            &ParseErrorKind::Empty
+        );
+        assert_eq!(
+            "H2".parse::<Note>().unwrap_err().kind(),
+            &ParseErrorKind::InvalidNote
+        );
+        assert_eq!(
+            "C#".parse::<Note>().unwrap_err().kind(),
+            &ParseErrorKind::InvalidNote
+        );
+        assert_eq!(
+            "A9".parse::<Note>().unwrap_err().kind(),
+            &ParseErrorKind::OutOfRange
+        );
+        for note_number in Note::MIN_NOTE_NUMBER..=Note::MAX_NOTE_NUMBER {
+            let note = Note::from_note_number(note_number).unwrap();
+            assert_eq!(note.to_string().parse(), Ok(note));
+        }
+    }
 
     #[test]
     fn test_octave() {
+        assert_eq!(Note::Cminus1.octave(), -1);
+        assert_eq!(Note::Aminus1.octave(), -1);
         assert_eq!(Note::C0.octave(), 0);
         assert_eq!(Note::A0.octave(), 0);
         assert_eq!(Note::C1.octave(), 1);
@@ -247,6 +458,8 @@ mod tests {
         assert_eq!(Note::A4.octave(), 4);
         assert_eq!(Note::C5.octave(), 5);
         assert_eq!(Note::A5.octave(), 5);
+        assert_eq!(Note::C9.octave(), 9);
+        assert_eq!(Note::G9.octave(), 9);
     }
 
     #[test]
@@ -261,14 +474,17 @@ mod tests {
         assert_eq!(Note::A3.octave_up(), Some(Note::A4));
         assert_eq!(Note::C4.octave_up(), Some(Note::C5));
         assert_eq!(Note::A4.octave_up(), Some(Note::A5));
-        assert_eq!(Note::C5.octave_up(), None);
-        assert_eq!(Note::A5.octave_up(), None);
+        assert_eq!(Note::C5.octave_up(), Some(Note::C6));
+        assert_eq!(Note::A5.octave_up(), Some(Note::A6));
+        assert_eq!(Note::G8.octave_up(), Some(Note::G9));
+        assert_eq!(Note::Gsharp8.octave_up(), None);
     }
 
     #[test]
     fn test_octave_down() {
-        assert_eq!(Note::C0.octave_down(), None);
-        assert_eq!(Note::A0.octave_down(), None);
+        assert_eq!(Note::Bminus1.octave_down(), None);
+        assert_eq!(Note::C0.octave_down(), Some(Note::Cminus1));
+        assert_eq!(Note::A0.octave_down(), Some(Note::Aminus1));
         assert_eq!(Note::C1.octave_down(), Some(Note::C0));
         assert_eq!(Note::A1.octave_down(), Some(Note::A0));
         assert_eq!(Note::C2.octave_down(), Some(Note::C1));
@@ -283,7 +499,8 @@ mod tests {
 
     #[test]
     fn test_transpose() {
-        assert_eq!(Note::C0.transpose(-1), None);
+        assert_eq!(Note::Cminus1.transpose(-1), None);
+        assert_eq!(Note::C0.transpose(-1), Some(Note::Bminus1));
         assert_eq!(Note::A0.transpose(2), Some(Note::B0));
         assert_eq!(Note::C1.transpose(3), Some(Note::Dsharp1));
         assert_eq!(Note::A1.transpose(-4), Some(Note::F1));
@@ -294,11 +511,56 @@ mod tests {
         assert_eq!(Note::C4.transpose(9), Some(Note::A4));
         assert_eq!(Note::A4.transpose(10), Some(Note::G5));
         assert_eq!(Note::C5.transpose(11), Some(Note::B5));
-        assert_eq!(Note::A5.transpose(12), None);
+        assert_eq!(Note::A5.transpose(12), Some(Note::A6));
+        assert_eq!(Note::G9.transpose(1), None);
+    }
+
+    #[test]
+    fn test_checked_transpose() {
+        assert_eq!(Note::Cminus1.checked_transpose(-1), None);
+        assert_eq!(Note::Cminus1.checked_transpose(127), Some(Note::G9));
+        assert_eq!(Note::G9.checked_transpose(-127), Some(Note::Cminus1));
+        assert_eq!(Note::G9.checked_transpose(1), None);
+        assert_eq!(Note::C4.checked_transpose(i8::MAX), None);
+        assert_eq!(Note::C4.checked_transpose(i8::MIN), None);
+    }
+
+    #[test]
+    fn test_saturating_transpose() {
+        assert_eq!(Note::C4.saturating_transpose(7), Note::G4);
+        assert_eq!(Note::Cminus1.saturating_transpose(-1), Note::Cminus1);
+        assert_eq!(Note::C0.saturating_transpose(-24), Note::Cminus1);
+        assert_eq!(Note::G9.saturating_transpose(1), Note::G9);
+        assert_eq!(Note::C9.saturating_transpose(i8::MAX), Note::G9);
+        assert_eq!(Note::C4.saturating_transpose(i8::MIN), Note::Cminus1);
+    }
+
+    #[test]
+    fn test_wrapping_transpose() {
+        assert_eq!(Note::C4.wrapping_transpose(7), Note::G4);
+        assert_eq!(Note::Cminus1.wrapping_transpose(-1), Note::G9);
+        assert_eq!(Note::G9.wrapping_transpose(1), Note::Cminus1);
+        assert_eq!(Note::G9.wrapping_transpose(2), Note::Csharpminus1);
+        assert_eq!(Note::C4.wrapping_transpose(i8::MIN), Note::C4);
+    }
+
+    #[test]
+    fn test_from_note_number() {
+        assert_eq!(Note::from_note_number(0), Some(Note::Cminus1));
+        assert_eq!(Note::from_note_number(60), Some(Note::C4));
+        assert_eq!(Note::from_note_number(127), Some(Note::G9));
+        assert_eq!(Note::from_note_number(128), None);
+        for note_number in 0..=127 {
+            assert_eq!(
+                Note::from_note_number(note_number).map(|note| note.note_number()),
+                Some(note_number)
+            );
+        }
     }
 
     #[test]
     fn test_freq() {
+        assert_eq!(Note::Cminus1.freq(), Frequency(8.175798));
         assert_eq!(Note::C0.freq(), Frequency(16.351597));
         assert_eq!(Note::A0.freq(), Frequency(27.5));
         assert_eq!(Note::C1.freq(), Frequency(32.703194));
@@ -311,10 +573,24 @@ mod tests {
         assert_eq!(Note::A4.freq(), Frequency(440.0));
         assert_eq!(Note::C5.freq(), Frequency(523.2511));
         assert_eq!(Note::A5.freq(), Frequency(880.0));
+        assert_eq!(Note::A8.freq(), Frequency(7040.0));
+        assert_eq!(Note::G9.freq(), Frequency(12543.855));
+    }
+
+    #[test]
+    fn test_freq_with_tuning() {
+        let tuning = Tuning::new(442.0, TuningSystem::EqualTemperament(12));
+        assert_eq!(Note::A4.freq_with_tuning(&tuning), Frequency(442.0));
+        assert_eq!(Note::A5.freq_with_tuning(&tuning), Frequency(884.0));
+        assert_eq!(
+            Note::A4.freq_with_tuning(&Tuning::default()),
+            Note::A4.freq()
+        );
     }
 
     #[test]
     fn test_pitch_class() {
+        assert_eq!(Note::Cminus1.pitch_class(), PitchClass::C);
         assert_eq!(Note::C0.pitch_class(), PitchClass::C);
         assert_eq!(Note::A0.pitch_class(), PitchClass::A);
         assert_eq!(Note::C1.pitch_class(), PitchClass::C);
@@ -327,10 +603,13 @@ mod tests {
         assert_eq!(Note::A4.pitch_class(), PitchClass::A);
         assert_eq!(Note::C5.pitch_class(), PitchClass::C);
         assert_eq!(Note::A5.pitch_class(), PitchClass::A);
+        assert_eq!(Note::G9.pitch_class(), PitchClass::G);
     }
 
     #[test]
     fn test_format() {
+        assert_eq!(Note::Cminus1.to_string(), "C-1");
+        assert_eq!(Note::Asharpminus1.to_string(), "A#-1");
         assert_eq!(Note::C1.to_string(), "C1");
         assert_eq!(Note::A1.to_string(), "A1");
         assert_eq!(Note::C2.to_string(), "C2");
@@ -341,5 +620,6 @@ mod tests {
         assert_eq!(Note::A4.to_string(), "A4");
         assert_eq!(Note::C5.to_string(), "C5");
         assert_eq!(Note::A5.to_string(), "A5");
+        assert_eq!(Note::G9.to_string(), "G9");
     }
 }
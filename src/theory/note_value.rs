@@ -0,0 +1,325 @@
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    iter::Sum,
+    ops::{Add, Mul},
+};
+
+use super::Duration;
+
+// NOTE: An exact length as a fraction of a whole note, always kept in lowest terms
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NoteValue {
+    numerator: u64,
+    denominator: u64,
+}
+
+impl NoteValue {
+    pub const ZERO: NoteValue = NoteValue {
+        numerator: 0,
+        denominator: 1,
+    };
+
+    const BEATS_PER_WHOLE: f64 = 4.0;
+
+    pub fn new(numerator: u64, denominator: u64) -> Option<Self> {
+        if denominator == 0 {
+            return None;
+        }
+        let gcd = Self::gcd(numerator, denominator);
+        Some(Self {
+            numerator: numerator / gcd,
+            denominator: denominator / gcd,
+        })
+    }
+
+    #[inline]
+    pub fn numerator(&self) -> u64 {
+        self.numerator
+    }
+
+    #[inline]
+    pub fn denominator(&self) -> u64 {
+        self.denominator
+    }
+
+    // NOTE: Each dot adds half of the previous addition, e.g. 1 dot is 3/2 and 2 dots are 7/4
+    pub fn with_dots(&self, dots: u32) -> Self {
+        let denominator = 1 << dots;
+        self.scale(2 * denominator - 1, denominator)
+    }
+
+    #[inline]
+    pub fn dotted(&self) -> Self {
+        self.with_dots(1)
+    }
+
+    #[inline]
+    pub fn double_dotted(&self) -> Self {
+        self.with_dots(2)
+    }
+
+    // NOTE: Fits `count` notes in the space of `space` notes, e.g. a triplet is tuplet(3, 2)
+    pub fn tuplet(&self, count: u64, space: u64) -> Option<Self> {
+        if count == 0 {
+            return None;
+        }
+        Some(self.scale(space, count))
+    }
+
+    #[inline]
+    pub fn tie(&self, other: &NoteValue) -> Self {
+        *self + *other
+    }
+
+    pub fn checked_add(&self, rhs: &NoteValue) -> Option<Self> {
+        let (lhs, rhs, denominator) = self.common_denominator(rhs)?;
+        Self::new(lhs.checked_add(rhs)?, denominator)
+    }
+
+    pub fn checked_sub(&self, rhs: &NoteValue) -> Option<Self> {
+        let (lhs, rhs, denominator) = self.common_denominator(rhs)?;
+        Self::new(lhs.checked_sub(rhs)?, denominator)
+    }
+
+    pub fn checked_div(&self, rhs: &NoteValue) -> Option<Self> {
+        // NOTE: Reduces across first, the result is already in lowest terms otherwise
+        let numerators = Self::gcd(self.numerator, rhs.numerator);
+        let denominators = Self::gcd(self.denominator, rhs.denominator);
+        Self::new(
+            (self.numerator / numerators).checked_mul(rhs.denominator / denominators)?,
+            (self.denominator / denominators).checked_mul(rhs.numerator / numerators)?,
+        )
+    }
+
+    // NOTE: Numerators over the least common denominator, which stays small for tuplets of the
+    //       same kind where the product of the denominators would overflow
+    fn common_denominator(&self, rhs: &NoteValue) -> Option<(u64, u64, u64)> {
+        let gcd = Self::gcd(self.denominator, rhs.denominator);
+        let denominator = (self.denominator / gcd).checked_mul(rhs.denominator)?;
+        Some((
+            self.numerator.checked_mul(denominator / self.denominator)?,
+            rhs.numerator.checked_mul(denominator / rhs.denominator)?,
+            denominator,
+        ))
+    }
+
+    #[inline]
+    pub fn is_integer(&self) -> bool {
+        self.denominator == 1
+    }
+
+    #[inline]
+    pub fn ceil(&self) -> u64 {
+        self.numerator.div_ceil(self.denominator)
+    }
+
+    #[inline]
+    pub fn as_f64(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+
+    // NOTE: The tempo is counted in quarter notes
+    #[inline]
+    pub fn seconds(&self, bpm: f32) -> f64 {
+        (60.0 / bpm as f64) * Self::BEATS_PER_WHOLE * self.as_f64()
+    }
+
+    fn scale(&self, numerator: u64, denominator: u64) -> Self {
+        let across = Self::gcd(self.numerator, denominator);
+        let within = Self::gcd(numerator, self.denominator);
+        Self::new(
+            (self.numerator / across) * (numerator / within),
+            (self.denominator / within) * (denominator / across),
+        )
+        .expect("should have non-zero denominator")
+    }
+
+    fn gcd(a: u64, b: u64) -> u64 {
+        if b == 0 {
+            a.max(1)
+        } else {
+            Self::gcd(b, a % b)
+        }
+    }
+}
+
+impl Default for NoteValue {
+    #[inline]
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl From<Duration> for NoteValue {
+    #[inline]
+    fn from(value: Duration) -> Self {
+        value.note_value()
+    }
+}
+
+impl Add for NoteValue {
+    type Output = NoteValue;
+
+    #[inline]
+    fn add(self, rhs: NoteValue) -> Self::Output {
+        self.checked_add(&rhs)
+            .expect("should fit the sum of note values")
+    }
+}
+
+impl Mul<u64> for NoteValue {
+    type Output = NoteValue;
+
+    #[inline]
+    fn mul(self, rhs: u64) -> Self::Output {
+        self.scale(rhs, 1)
+    }
+}
+
+impl Sum for NoteValue {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, value| acc + value)
+    }
+}
+
+impl PartialOrd for NoteValue {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for NoteValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.numerator as u128 * other.denominator as u128)
+            .cmp(&(other.numerator as u128 * self.denominator as u128))
+    }
+}
+
+impl Display for NoteValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.numerator, self.denominator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert_eq!(NoteValue::new(2, 8), NoteValue::new(1, 4));
+        assert_eq!(NoteValue::new(0, 5), Some(NoteValue::ZERO));
+        assert_eq!(NoteValue::new(1, 0), None);
+    }
+
+    #[test]
+    fn test_dots() {
+        let quarter = Duration::Quarter.note_value();
+        assert_eq!(quarter.dotted(), NoteValue::new(3, 8).unwrap());
+        assert_eq!(quarter.double_dotted(), NoteValue::new(7, 16).unwrap());
+        assert_eq!(quarter.with_dots(0), quarter);
+        assert_eq!(Duration::Half.dotted(), NoteValue::new(3, 4).unwrap());
+    }
+
+    #[test]
+    fn test_tuplet() {
+        let eighth = Duration::Eighth.note_value();
+        let triplet = eighth.tuplet(3, 2).unwrap();
+        assert_eq!(triplet, NoteValue::new(1, 12).unwrap());
+        assert_eq!(triplet * 3, Duration::Quarter.note_value());
+
+        let quintuplet = Duration::Sixteenth.tuplet(5, 4).unwrap();
+        assert_eq!(quintuplet * 5, Duration::Quarter.note_value());
+        assert_eq!(eighth.tuplet(0, 2), None);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let triplet = Duration::Eighth.tuplet(3, 2).unwrap();
+        let sum: NoteValue = std::iter::repeat_n(triplet, 12).sum();
+        assert_eq!(sum, Duration::Whole.note_value());
+
+        let tied = Duration::Quarter
+            .note_value()
+            .tie(&Duration::Eighth.note_value());
+        assert_eq!(tied, Duration::Quarter.dotted());
+        assert_eq!(
+            tied.checked_sub(&Duration::Eighth.note_value()),
+            Some(Duration::Quarter.note_value())
+        );
+        assert_eq!(
+            Duration::Eighth
+                .note_value()
+                .checked_sub(&Duration::Quarter.note_value()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_large_denominators() {
+        // NOTE: The product of these denominators is past u64::MAX, their lcm is not
+        let small = NoteValue::new(1, 3_u64.pow(20)).unwrap();
+        let smaller = NoteValue::new(1, 2 * 3_u64.pow(20)).unwrap();
+        assert_eq!(
+            small + smaller,
+            NoteValue::new(1, 2 * 3_u64.pow(19)).unwrap()
+        );
+        assert_eq!(small.checked_sub(&smaller), Some(smaller));
+        assert_eq!(small.checked_div(&smaller), NoteValue::new(2, 1));
+
+        let septuplet = Duration::Sixteenth.tuplet(7, 4).unwrap();
+        let position: NoteValue = std::iter::repeat_n(septuplet, 7 * 10_000).sum();
+        assert_eq!(position, NoteValue::new(2_500, 1).unwrap());
+
+        let huge = NoteValue::new(1, u64::MAX).unwrap();
+        assert_eq!(
+            huge.checked_add(&NoteValue::new(1, u64::MAX - 1).unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_div() {
+        let measure = Duration::Eighth.note_value() * 7;
+        let steps = measure
+            .checked_div(&Duration::Quarter.note_value())
+            .unwrap();
+        assert_eq!(steps, NoteValue::new(7, 2).unwrap());
+        assert!(!steps.is_integer());
+        assert_eq!(steps.ceil(), 4);
+        assert_eq!(measure.checked_div(&NoteValue::ZERO), None);
+    }
+
+    #[test]
+    fn test_ord() {
+        let triplet = Duration::Quarter.tuplet(3, 2).unwrap();
+        assert!(triplet < Duration::Quarter.note_value());
+        assert!(triplet > Duration::Eighth.note_value());
+        assert!(Duration::Eighth.dotted() < Duration::Quarter.note_value());
+        assert_eq!(
+            Duration::Eighth
+                .dotted()
+                .cmp(&NoteValue::new(6, 32).unwrap()),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn test_seconds() {
+        assert_eq!(Duration::Quarter.note_value().seconds(60.0), 1.0);
+        assert_eq!(Duration::Quarter.dotted().seconds(120.0), 0.75);
+        assert_eq!(Duration::Whole.note_value().seconds(120.0), 2.0);
+        assert_eq!(
+            Duration::Quarter.tuplet(3, 2).unwrap().seconds(90.0),
+            60.0 / 90.0 * 2.0 / 3.0
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Duration::Quarter.dotted().to_string(), "3/8");
+        assert_eq!(NoteValue::ZERO.to_string(), "0/1");
+    }
+}
@@ -0,0 +1,56 @@
+use std::fmt::Display;
+
+use wasm_bindgen::JsValue;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    Empty,
+    InvalidRoot,
+    InvalidNote,
+    InvalidQuality,
+    InvalidBass,
+    InvalidNumeral,
+    InvalidTimeSignature,
+    Unsupported,
+    OutOfRange,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    message: String,
+}
+
+impl ParseError {
+    pub fn new<S: Into<String>>(kind: ParseErrorKind, message: S) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    #[inline]
+    pub fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<ParseError> for JsValue {
+    #[inline]
+    fn from(value: ParseError) -> Self {
+        JsValue::from_str(&value.to_string())
+    }
+}
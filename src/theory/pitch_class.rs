@@ -1,4 +1,6 @@
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
+
+use super::{ParseError, SpelledPitchClass};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[allow(dead_code)]
@@ -17,6 +19,61 @@ pub enum PitchClass {
     B,
 }
 
+impl PitchClass {
+    pub fn from_semitones(semitones: u8) -> Self {
+        match semitones % 12 {
+            0 => PitchClass::C,
+            1 => PitchClass::CSharp,
+            2 => PitchClass::D,
+            3 => PitchClass::DSharp,
+            4 => PitchClass::E,
+            5 => PitchClass::F,
+            6 => PitchClass::FSharp,
+            7 => PitchClass::G,
+            8 => PitchClass::GSharp,
+            9 => PitchClass::A,
+            10 => PitchClass::ASharp,
+            11 => PitchClass::B,
+            _ => unreachable!("unsupported pitch class"),
+        }
+    }
+
+    pub fn semitones(&self) -> u8 {
+        match self {
+            PitchClass::C => 0,
+            PitchClass::CSharp => 1,
+            PitchClass::D => 2,
+            PitchClass::DSharp => 3,
+            PitchClass::E => 4,
+            PitchClass::F => 5,
+            PitchClass::FSharp => 6,
+            PitchClass::G => 7,
+            PitchClass::GSharp => 8,
+            PitchClass::A => 9,
+            PitchClass::ASharp => 10,
+            PitchClass::B => 11,
+        }
+    }
+}
+
+impl FromStr for PitchClass {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let spelled: SpelledPitchClass = s.parse()?;
+        Ok(spelled.pitch_class())
+    }
+}
+
+impl TryFrom<&str> for PitchClass {
+    type Error = ParseError;
+
+    #[inline]
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
 impl Display for PitchClass {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let s = match self {
@@ -36,3 +93,21 @@ impl Display for PitchClass {
         write!(f, "{s}")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("C".parse(), Ok(PitchClass::C));
+        assert_eq!("c#".parse(), Ok(PitchClass::CSharp));
+        assert_eq!("Db".parse(), Ok(PitchClass::CSharp));
+        assert_eq!("B♭".parse(), Ok(PitchClass::ASharp));
+        assert_eq!("Cb".parse(), Ok(PitchClass::B));
+        assert_eq!("E##".parse(), Ok(PitchClass::FSharp));
+        assert_eq!(PitchClass::try_from("G♯"), Ok(PitchClass::GSharp));
+        assert!("C4".parse::<PitchClass>().is_err());
+        assert!("".parse::<PitchClass>().is_err());
+    }
+}
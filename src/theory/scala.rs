@@ -0,0 +1,419 @@
+use crate::line_error::LineError;
+
+// NOTE: Yields (line number, line) pairs, skipping `!` comments
+struct Lines<'a> {
+    inner: std::iter::Enumerate<std::str::Lines<'a>>,
+    last_line: usize,
+}
+
+impl<'a> Lines<'a> {
+    fn new(s: &'a str) -> Self {
+        Self {
+            inner: s.lines().enumerate(),
+            last_line: 0,
+        }
+    }
+
+    fn next_line(&mut self, skip_blank: bool) -> Option<(usize, &'a str)> {
+        for (i, line) in self.inner.by_ref() {
+            self.last_line = i + 1;
+            if line.starts_with('!') || (skip_blank && line.trim().is_empty()) {
+                continue;
+            }
+            return Some((i + 1, line));
+        }
+        None
+    }
+
+    fn expect_token(&mut self, name: &str) -> Result<(usize, &'a str), LineError> {
+        let (line, s) = self
+            .next_line(true)
+            .ok_or_else(|| LineError::new(self.last_line + 1, format!("missing {name}")))?;
+        let token = s
+            .split_whitespace()
+            .next()
+            .expect("should be non-blank line");
+        Ok((line, token))
+    }
+
+    fn expect_number<T: std::str::FromStr>(&mut self, name: &str) -> Result<T, LineError> {
+        let (line, token) = self.expect_token(name)?;
+        token
+            .parse()
+            .map_err(|_| LineError::new(line, format!("invalid {name}: {token}")))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScalaScale {
+    description: String,
+    ratios: Vec<f64>,
+}
+
+impl ScalaScale {
+    pub fn new<S: Into<String>>(description: S, ratios: Vec<f64>) -> Self {
+        Self {
+            description: description.into(),
+            ratios,
+        }
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self, LineError> {
+        let s = String::from_utf8_lossy(data);
+        let mut lines = Lines::new(&s);
+
+        let (_, description) = lines
+            .next_line(false)
+            .ok_or_else(|| LineError::new(1, "missing description"))?;
+        let count: usize = lines.expect_number("note count")?;
+        if count == 0 {
+            return Err(LineError::new(
+                lines.last_line,
+                "note count should be greater than 0",
+            ));
+        }
+
+        let ratios = (0..count)
+            .map(|_| {
+                let (line, token) = lines.expect_token("pitch")?;
+                Self::parse_pitch(token)
+                    .ok_or_else(|| LineError::new(line, format!("invalid pitch: {token}")))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self::new(description.trim(), ratios))
+    }
+
+    fn parse_pitch(token: &str) -> Option<f64> {
+        let ratio = if token.contains('.') {
+            let cents: f64 = token.parse().ok()?;
+            (2.0_f64).powf(cents / 1200.0)
+        } else if let Some((numerator, denominator)) = token.split_once('/') {
+            let numerator: u64 = numerator.parse().ok()?;
+            let denominator: u64 = denominator.parse().ok()?;
+            numerator as f64 / denominator as f64
+        } else {
+            token.parse::<u64>().ok()? as f64
+        };
+
+        if ratio.is_finite() && ratio > 0.0 {
+            Some(ratio)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ratios.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ratios.is_empty()
+    }
+
+    #[inline]
+    pub fn period(&self) -> f64 {
+        self.ratios.last().copied().unwrap_or(2.0)
+    }
+
+    pub fn ratio(&self, degree: i32) -> f64 {
+        if self.ratios.is_empty() {
+            return 1.0;
+        }
+
+        let len = self.ratios.len() as i32;
+        let periods = degree.div_euclid(len);
+        let step = degree.rem_euclid(len);
+        let ratio = if step == 0 {
+            1.0
+        } else {
+            self.ratios[step as usize - 1]
+        };
+        ratio * self.period().powi(periods)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyboardMapping {
+    first_note: u8,
+    last_note: u8,
+    middle_note: u8,
+    reference_note: u8,
+    reference_freq: f64,
+    octave_degree: usize,
+    mapping: Vec<Option<usize>>,
+}
+
+impl KeyboardMapping {
+    pub fn linear(middle_note: u8, reference_note: u8, reference_freq: f64) -> Self {
+        Self {
+            first_note: 0,
+            last_note: 127,
+            middle_note,
+            reference_note,
+            reference_freq,
+            octave_degree: 0,
+            mapping: vec![],
+        }
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self, LineError> {
+        let s = String::from_utf8_lossy(data);
+        let mut lines = Lines::new(&s);
+
+        let size: usize = lines.expect_number("map size")?;
+        let first_note = Self::parse_note_number(&mut lines, "first note")?;
+        let last_note = Self::parse_note_number(&mut lines, "last note")?;
+        let middle_note = Self::parse_note_number(&mut lines, "middle note")?;
+        let reference_note = Self::parse_note_number(&mut lines, "reference note")?;
+        let reference_line = lines.last_line;
+        let reference_freq: f64 = lines.expect_number("reference frequency")?;
+        if !(reference_freq.is_finite() && reference_freq > 0.0) {
+            return Err(LineError::new(
+                lines.last_line,
+                "reference frequency should be positive",
+            ));
+        }
+        let octave_degree: usize = lines.expect_number("octave degree")?;
+
+        let mut mapping = Vec::with_capacity(size);
+        while mapping.len() < size {
+            let Some((line, s)) = lines.next_line(true) else {
+                break;
+            };
+            let token = s
+                .split_whitespace()
+                .next()
+                .expect("should be non-blank line");
+            if token == "x" {
+                mapping.push(None);
+            } else {
+                let degree = token
+                    .parse()
+                    .map_err(|_| LineError::new(line, format!("invalid mapping: {token}")))?;
+                mapping.push(Some(degree));
+            }
+        }
+        mapping.resize(size, None);
+
+        let kbm = Self {
+            first_note,
+            last_note,
+            middle_note,
+            reference_note,
+            reference_freq,
+            octave_degree,
+            mapping,
+        };
+        // NOTE: Every other key is tuned relative to the reference, so it cannot be left unmapped
+        if !kbm.is_mapped(reference_note) {
+            return Err(LineError::new(
+                reference_line,
+                format!("reference note should be mapped: {reference_note}"),
+            ));
+        }
+        Ok(kbm)
+    }
+
+    fn parse_note_number(lines: &mut Lines, name: &str) -> Result<u8, LineError> {
+        let note_number: u8 = lines.expect_number(name)?;
+        if note_number > 127 {
+            return Err(LineError::new(
+                lines.last_line,
+                format!("{name} should be in 0..=127: {note_number}"),
+            ));
+        }
+        Ok(note_number)
+    }
+
+    #[inline]
+    pub fn reference_note(&self) -> u8 {
+        self.reference_note
+    }
+
+    #[inline]
+    pub fn reference_freq(&self) -> f64 {
+        self.reference_freq
+    }
+
+    fn is_mapped(&self, note_number: u8) -> bool {
+        if !(self.first_note..=self.last_note).contains(&note_number) {
+            return false;
+        }
+        if self.mapping.is_empty() {
+            return true;
+        }
+        let offset = note_number as i32 - self.middle_note as i32;
+        let size = self.mapping.len() as i32;
+        self.mapping[offset.rem_euclid(size) as usize].is_some()
+    }
+
+    // NOTE: Returns the ratio to the middle note, or None for unmapped keys
+    pub fn ratio(&self, scale: &ScalaScale, note_number: u8) -> Option<f64> {
+        if !(self.first_note..=self.last_note).contains(&note_number) {
+            return None;
+        }
+
+        let offset = note_number as i32 - self.middle_note as i32;
+        if self.mapping.is_empty() {
+            return Some(scale.ratio(offset));
+        }
+
+        let size = self.mapping.len() as i32;
+        let periods = offset.div_euclid(size);
+        let degree = self.mapping[offset.rem_euclid(size) as usize]?;
+        let period = scale.ratio(self.octave_degree as i32);
+        Some(scale.ratio(degree as i32) * period.powi(periods))
+    }
+}
+
+impl Default for KeyboardMapping {
+    fn default() -> Self {
+        Self::linear(60, 69, 440.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MEANTONE: &[u8] = b"! meanquar.scl
+!
+1/4-comma meantone scale. Pietro Aaron's temperament (1523)
+ 12
+!
+ 76.04900
+ 193.15686
+ 310.26471
+ 5/4
+ 503.42157
+ 579.47057
+ 696.57843
+ 25/16
+ 889.73529
+ 1006.84314
+ 1082.89214
+ 2/1
+";
+
+    const WHITE_KEYS: &[u8] = b"! white.kbm
+! Size of map:
+7
+! First MIDI note number to retune:
+0
+! Last MIDI note number to retune:
+127
+! Middle note where the first entry of the mapping is mapped to:
+60
+! Reference note for which frequency is given:
+69
+! Frequency to tune the above note to
+440.0
+! Scale degree to consider as formal octave:
+7
+! Mapping.
+0
+x
+1
+x
+2
+3
+x
+";
+
+    #[test]
+    fn test_scale_from_bytes() {
+        let scale = ScalaScale::from_bytes(MEANTONE).unwrap();
+        assert_eq!(
+            scale.description(),
+            "1/4-comma meantone scale. Pietro Aaron's temperament (1523)"
+        );
+        assert_eq!(scale.len(), 12);
+        assert_eq!(scale.ratio(0), 1.0);
+        assert_eq!(scale.ratio(4), 1.25);
+        assert_eq!(scale.ratio(12), 2.0);
+        assert_eq!(scale.ratio(16), 2.5);
+        assert_eq!(scale.ratio(-8), 0.625);
+        assert!((scale.ratio(1) - 1.044907).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_scale_from_bytes_integer_and_trailing_text() {
+        let scale = ScalaScale::from_bytes(b"\n2\n3/2 fifth\n2 octave\n").unwrap();
+        assert_eq!(scale.description(), "");
+        assert_eq!(scale.ratio(1), 1.5);
+        assert_eq!(scale.ratio(2), 2.0);
+    }
+
+    #[test]
+    fn test_scale_from_bytes_errors() {
+        assert_eq!(
+            ScalaScale::from_bytes(b"").unwrap_err(),
+            LineError::new(1, "missing description")
+        );
+        assert_eq!(
+            ScalaScale::from_bytes(b"test\nfoo\n").unwrap_err(),
+            LineError::new(2, "invalid note count: foo")
+        );
+        assert_eq!(
+            ScalaScale::from_bytes(b"test\n0\n").unwrap_err(),
+            LineError::new(2, "note count should be greater than 0")
+        );
+        assert_eq!(
+            ScalaScale::from_bytes(b"test\n2\n! comment\n3/0\n2/1\n").unwrap_err(),
+            LineError::new(4, "invalid pitch: 3/0")
+        );
+        assert_eq!(
+            ScalaScale::from_bytes(b"test\n2\n3/2\n").unwrap_err(),
+            LineError::new(4, "missing pitch")
+        );
+    }
+
+    #[test]
+    fn test_keyboard_mapping_from_bytes() {
+        let kbm = KeyboardMapping::from_bytes(WHITE_KEYS).unwrap();
+        assert_eq!(kbm.reference_note(), 69);
+        assert_eq!(kbm.reference_freq(), 440.0);
+
+        let scale =
+            ScalaScale::from_bytes(b"major\n7\n9/8\n5/4\n4/3\n3/2\n5/3\n15/8\n2/1\n").unwrap();
+        assert_eq!(kbm.ratio(&scale, 60), Some(1.0));
+        assert_eq!(kbm.ratio(&scale, 61), None);
+        assert_eq!(kbm.ratio(&scale, 62), Some(9.0 / 8.0));
+        assert_eq!(kbm.ratio(&scale, 65), Some(4.0 / 3.0));
+        assert_eq!(kbm.ratio(&scale, 67), Some(2.0));
+        assert_eq!(kbm.ratio(&scale, 53), Some(0.5));
+    }
+
+    #[test]
+    fn test_keyboard_mapping_from_bytes_errors() {
+        assert_eq!(
+            KeyboardMapping::from_bytes(b"0\n0\n127\n60\n69\n").unwrap_err(),
+            LineError::new(6, "missing reference frequency")
+        );
+        assert_eq!(
+            KeyboardMapping::from_bytes(b"0\n0\n128\n60\n69\n440.0\n12\n").unwrap_err(),
+            LineError::new(3, "last note should be in 0..=127: 128")
+        );
+        assert_eq!(
+            KeyboardMapping::from_bytes(b"2\n0\n127\n60\n69\n440.0\n12\n0\ny\n").unwrap_err(),
+            LineError::new(9, "invalid mapping: y")
+        );
+    }
+
+    #[test]
+    fn test_keyboard_mapping_linear() {
+        let kbm = KeyboardMapping::default();
+        let scale = ScalaScale::from_bytes(MEANTONE).unwrap();
+        assert_eq!(kbm.ratio(&scale, 60), Some(1.0));
+        assert_eq!(kbm.ratio(&scale, 64), Some(1.25));
+        assert_eq!(kbm.ratio(&scale, 48), Some(0.5));
+    }
+}
@@ -0,0 +1,389 @@
+use std::fmt::Display;
+
+use super::{Interval, Note, PitchClass};
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Scale {
+    Major,
+    NaturalMinor,
+    HarmonicMinor,
+    MelodicMinor,
+    Ionian,
+    Dorian,
+    Phrygian,
+    Lydian,
+    Mixolydian,
+    Aeolian,
+    Locrian,
+    MajorPentatonic,
+    MinorPentatonic,
+    Blues,
+    WholeTone,
+    Diminished,
+    Custom(Vec<Interval>),
+}
+
+impl Scale {
+    const SEMITONES: u8 = 12;
+
+    pub fn intervals(&self) -> Vec<Interval> {
+        match self {
+            Scale::Major | Scale::Ionian => vec![
+                Interval::P1,
+                Interval::Maj2,
+                Interval::Maj3,
+                Interval::P4,
+                Interval::P5,
+                Interval::Maj6,
+                Interval::Maj7,
+            ],
+            Scale::NaturalMinor | Scale::Aeolian => vec![
+                Interval::P1,
+                Interval::Maj2,
+                Interval::Min3,
+                Interval::P4,
+                Interval::P5,
+                Interval::Min6,
+                Interval::Min7,
+            ],
+            Scale::HarmonicMinor => vec![
+                Interval::P1,
+                Interval::Maj2,
+                Interval::Min3,
+                Interval::P4,
+                Interval::P5,
+                Interval::Min6,
+                Interval::Maj7,
+            ],
+            Scale::MelodicMinor => vec![
+                Interval::P1,
+                Interval::Maj2,
+                Interval::Min3,
+                Interval::P4,
+                Interval::P5,
+                Interval::Maj6,
+                Interval::Maj7,
+            ],
+            Scale::Dorian => vec![
+                Interval::P1,
+                Interval::Maj2,
+                Interval::Min3,
+                Interval::P4,
+                Interval::P5,
+                Interval::Maj6,
+                Interval::Min7,
+            ],
+            Scale::Phrygian => vec![
+                Interval::P1,
+                Interval::Min2,
+                Interval::Min3,
+                Interval::P4,
+                Interval::P5,
+                Interval::Min6,
+                Interval::Min7,
+            ],
+            Scale::Lydian => vec![
+                Interval::P1,
+                Interval::Maj2,
+                Interval::Maj3,
+                Interval::A4,
+                Interval::P5,
+                Interval::Maj6,
+                Interval::Maj7,
+            ],
+            Scale::Mixolydian => vec![
+                Interval::P1,
+                Interval::Maj2,
+                Interval::Maj3,
+                Interval::P4,
+                Interval::P5,
+                Interval::Maj6,
+                Interval::Min7,
+            ],
+            Scale::Locrian => vec![
+                Interval::P1,
+                Interval::Min2,
+                Interval::Min3,
+                Interval::P4,
+                Interval::D5,
+                Interval::Min6,
+                Interval::Min7,
+            ],
+            Scale::MajorPentatonic => vec![
+                Interval::P1,
+                Interval::Maj2,
+                Interval::Maj3,
+                Interval::P5,
+                Interval::Maj6,
+            ],
+            Scale::MinorPentatonic => vec![
+                Interval::P1,
+                Interval::Min3,
+                Interval::P4,
+                Interval::P5,
+                Interval::Min7,
+            ],
+            Scale::Blues => vec![
+                Interval::P1,
+                Interval::Min3,
+                Interval::P4,
+                Interval::D5,
+                Interval::P5,
+                Interval::Min7,
+            ],
+            Scale::WholeTone => vec![
+                Interval::P1,
+                Interval::Maj2,
+                Interval::Maj3,
+                Interval::A4,
+                Interval::A5,
+                Interval::Min7,
+            ],
+            Scale::Diminished => vec![
+                Interval::P1,
+                Interval::Maj2,
+                Interval::Min3,
+                Interval::P4,
+                Interval::D5,
+                Interval::Min6,
+                Interval::Maj6,
+                Interval::Maj7,
+            ],
+            Scale::Custom(intervals) => intervals.clone(),
+        }
+    }
+
+    pub fn notes(&self, root: &Note, octaves: usize) -> Vec<Note> {
+        let intervals = self.intervals();
+        (0..octaves)
+            .flat_map(|octave| {
+                intervals.iter().map(move |interval| {
+                    octave * Self::SEMITONES as usize + interval.semitones() as usize
+                })
+            })
+            .filter_map(|semitones| {
+                let note_number = root.note_number() as usize + semitones;
+                u8::try_from(note_number)
+                    .ok()
+                    .and_then(Note::from_note_number)
+            })
+            .collect()
+    }
+
+    // NOTE: Degrees are 1-based (e.g. 5 is the dominant in a diatonic scale)
+    pub fn degree(&self, n: usize) -> Option<Interval> {
+        if n == 0 {
+            return None;
+        }
+        self.intervals().get(n - 1).cloned()
+    }
+
+    pub fn pitch_classes(&self, tonic: &PitchClass) -> Vec<PitchClass> {
+        self.intervals()
+            .iter()
+            .map(|interval| {
+                PitchClass::from_semitones(
+                    (tonic.semitones() + interval.semitones() % Self::SEMITONES) % Self::SEMITONES,
+                )
+            })
+            .collect()
+    }
+
+    pub fn contains(&self, tonic: &PitchClass, note: &Note) -> bool {
+        self.pitch_classes(tonic).contains(&note.pitch_class())
+    }
+
+    // NOTE: Ties are resolved towards the lower scale tone
+    pub fn quantize(&self, tonic: &PitchClass, note: &Note) -> Option<Note> {
+        let pitch_classes = self.pitch_classes(tonic);
+        let half = (Self::SEMITONES / 2) as i8;
+        (0..=half)
+            .flat_map(|distance| [-distance, distance])
+            .filter_map(|semitones| note.checked_transpose(semitones))
+            .find(|candidate| pitch_classes.contains(&candidate.pitch_class()))
+    }
+}
+
+impl Display for Scale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Scale::Major => "major".to_string(),
+            Scale::NaturalMinor => "natural minor".to_string(),
+            Scale::HarmonicMinor => "harmonic minor".to_string(),
+            Scale::MelodicMinor => "melodic minor".to_string(),
+            Scale::Ionian => "ionian".to_string(),
+            Scale::Dorian => "dorian".to_string(),
+            Scale::Phrygian => "phrygian".to_string(),
+            Scale::Lydian => "lydian".to_string(),
+            Scale::Mixolydian => "mixolydian".to_string(),
+            Scale::Aeolian => "aeolian".to_string(),
+            Scale::Locrian => "locrian".to_string(),
+            Scale::MajorPentatonic => "major pentatonic".to_string(),
+            Scale::MinorPentatonic => "minor pentatonic".to_string(),
+            Scale::Blues => "blues".to_string(),
+            Scale::WholeTone => "whole tone".to_string(),
+            Scale::Diminished => "diminished".to_string(),
+            Scale::Custom(intervals) => {
+                let intervals: Vec<String> = intervals
+                    .iter()
+                    .map(|interval| interval.to_string())
+                    .collect();
+                format!("custom ({})", intervals.join(" "))
+            }
+        };
+        write!(f, "{s}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Scale::Major.to_string(), "major");
+        assert_eq!(Scale::HarmonicMinor.to_string(), "harmonic minor");
+        assert_eq!(Scale::Mixolydian.to_string(), "mixolydian");
+        assert_eq!(Scale::MinorPentatonic.to_string(), "minor pentatonic");
+        assert_eq!(
+            Scale::Custom(vec![Interval::P1, Interval::Maj3, Interval::P5]).to_string(),
+            "custom (P1 M3 P5)"
+        );
+    }
+
+    #[test]
+    fn test_notes() {
+        assert_eq!(
+            Scale::Major.notes(&Note::C4, 1),
+            vec![
+                Note::C4,
+                Note::D4,
+                Note::E4,
+                Note::F4,
+                Note::G4,
+                Note::A4,
+                Note::B4
+            ]
+        );
+        assert_eq!(
+            Scale::NaturalMinor.notes(&Note::A3, 1),
+            vec![
+                Note::A3,
+                Note::B3,
+                Note::C4,
+                Note::D4,
+                Note::E4,
+                Note::F4,
+                Note::G4
+            ]
+        );
+        assert_eq!(
+            Scale::Dorian.notes(&Note::D4, 1),
+            vec![
+                Note::D4,
+                Note::E4,
+                Note::F4,
+                Note::G4,
+                Note::A4,
+                Note::B4,
+                Note::C5
+            ]
+        );
+        assert_eq!(
+            Scale::MajorPentatonic.notes(&Note::C4, 2),
+            vec![
+                Note::C4,
+                Note::D4,
+                Note::E4,
+                Note::G4,
+                Note::A4,
+                Note::C5,
+                Note::D5,
+                Note::E5,
+                Note::G5,
+                Note::A5
+            ]
+        );
+        assert_eq!(
+            Scale::Blues.notes(&Note::A3, 1),
+            vec![
+                Note::A3,
+                Note::C4,
+                Note::D4,
+                Note::Dsharp4,
+                Note::E4,
+                Note::G4
+            ]
+        );
+        assert_eq!(Scale::Major.notes(&Note::C4, 0), vec![]);
+        assert_eq!(
+            Scale::MajorPentatonic.notes(&Note::C9, 2),
+            vec![Note::C9, Note::D9, Note::E9, Note::G9]
+        );
+    }
+
+    #[test]
+    fn test_degree() {
+        assert_eq!(Scale::Major.degree(0), None);
+        assert_eq!(Scale::Major.degree(1), Some(Interval::P1));
+        assert_eq!(Scale::Major.degree(5), Some(Interval::P5));
+        assert_eq!(Scale::Lydian.degree(4), Some(Interval::A4));
+        assert_eq!(Scale::Major.degree(8), None);
+    }
+
+    #[test]
+    fn test_pitch_classes() {
+        assert_eq!(
+            Scale::MajorPentatonic.pitch_classes(&PitchClass::A),
+            vec![
+                PitchClass::A,
+                PitchClass::B,
+                PitchClass::CSharp,
+                PitchClass::E,
+                PitchClass::FSharp
+            ]
+        );
+    }
+
+    #[test]
+    fn test_contains() {
+        assert!(Scale::Major.contains(&PitchClass::C, &Note::E2));
+        assert!(!Scale::Major.contains(&PitchClass::C, &Note::Dsharp6));
+        assert!(Scale::HarmonicMinor.contains(&PitchClass::A, &Note::Gsharp1));
+        assert!(Scale::Major.contains(&PitchClass::FSharp, &Note::F9));
+        assert!(!Scale::Custom(vec![]).contains(&PitchClass::C, &Note::C4));
+    }
+
+    #[test]
+    fn test_quantize() {
+        assert_eq!(
+            Scale::Major.quantize(&PitchClass::C, &Note::E4),
+            Some(Note::E4)
+        );
+        assert_eq!(
+            Scale::Major.quantize(&PitchClass::C, &Note::Csharp4),
+            Some(Note::C4)
+        );
+        assert_eq!(
+            Scale::MajorPentatonic.quantize(&PitchClass::C, &Note::F4),
+            Some(Note::E4)
+        );
+        assert_eq!(
+            Scale::MajorPentatonic.quantize(&PitchClass::C, &Note::Asharp2),
+            Some(Note::A2)
+        );
+        assert_eq!(
+            Scale::MinorPentatonic.quantize(&PitchClass::A, &Note::B3),
+            Some(Note::C4)
+        );
+        assert_eq!(
+            Scale::Major.quantize(&PitchClass::C, &Note::Dsharp4),
+            Some(Note::D4)
+        );
+        assert_eq!(
+            Scale::Custom(vec![]).quantize(&PitchClass::C, &Note::C4),
+            None
+        );
+    }
+}
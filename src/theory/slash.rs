@@ -0,0 +1,236 @@
+use std::{fmt::Display, str::FromStr};
+
+use super::{Chord, ChordLike, Note, ParseError, ParseErrorKind, Spelled};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Slash<T>(pub T, pub Note);
+
+impl<T> Slash<T> {
+    #[inline]
+    pub fn bass_note(&self) -> Note {
+        self.1.clone()
+    }
+}
+
+impl<T: ChordLike> Slash<T> {
+    fn over_bass(&self, mut upper: Vec<Note>) -> Vec<Note> {
+        let mut bass = self.1.clone();
+        // NOTE: Keep the bass below the upper structure, raising the upper structure when the bass
+        //       is already in the lowest octave
+        while upper.iter().min().is_some_and(|lowest| bass >= *lowest) {
+            if let Some(note) = bass.octave_down() {
+                bass = note;
+                continue;
+            }
+            match upper
+                .iter()
+                .map(Note::octave_up)
+                .collect::<Option<Vec<_>>>()
+            {
+                Some(notes) => upper = notes,
+                None => break,
+            }
+        }
+
+        let mut notes = vec![bass];
+        notes.extend(upper);
+        notes
+    }
+}
+
+impl<T: ChordLike> ChordLike for Slash<T> {
+    #[inline]
+    fn notes(&self) -> Vec<Note> {
+        self.over_bass(self.0.notes())
+    }
+
+    // NOTE: Inverts the upper structure and keeps the bass below it
+    #[inline]
+    fn inverted(&self, inversion: usize) -> Vec<Note> {
+        self.over_bass(self.0.inverted(inversion))
+    }
+
+    #[inline]
+    fn root_note(&self) -> Note {
+        self.0.root_note()
+    }
+}
+
+impl<T: Display> Display for Slash<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.0, self.1.pitch_class())
+    }
+}
+
+// NOTE: The bass is spelled from the chord's own spelling, e.g. Bbm7/Ab rather than A#m7/G#
+impl Display for Spelled<'_, Slash<Chord>> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Slash(chord, bass) = self.0;
+        write!(f, "{}/{}", Spelled(chord), chord.spell(&bass.pitch_class()))
+    }
+}
+
+impl Slash<Chord> {
+    pub fn parse(s: &str, octave: i8) -> Result<Self, ParseError> {
+        let (chord, bass) = s.split_once('/').ok_or_else(|| {
+            ParseError::new(
+                ParseErrorKind::InvalidBass,
+                format!("missing bass note: {s}"),
+            )
+        })?;
+        let chord = Chord::parse(chord, octave)?;
+
+        let invalid_bass =
+            || ParseError::new(ParseErrorKind::InvalidBass, format!("invalid bass: {bass}"));
+        let (pitch_class, accidental, rest) = Chord::parse_root(bass).ok_or_else(invalid_bass)?;
+        if !rest.is_empty() {
+            return Err(invalid_bass());
+        }
+        let bass = Note::from_pitch_class(&pitch_class, octave)
+            .and_then(|note| note.checked_transpose(accidental))
+            .ok_or_else(|| {
+                ParseError::new(
+                    ParseErrorKind::OutOfRange,
+                    format!("octave out of range: {octave}"),
+                )
+            })?;
+
+        Ok(Slash(chord, bass))
+    }
+}
+
+impl FromStr for Slash<Chord> {
+    type Err = ParseError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, Chord::DEFAULT_OCTAVE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{arps::UpArpeggiator, theory::Inversion};
+
+    use super::*;
+
+    #[test]
+    fn test_notes() {
+        assert_eq!(
+            Slash(Chord::Major(Note::C3), Note::E3).notes(),
+            vec![Note::E2, Note::C3, Note::E3, Note::G3]
+        );
+        assert_eq!(
+            Slash(Chord::Minor7th(Note::A3), Note::G3).notes(),
+            vec![Note::G3, Note::A3, Note::C4, Note::E4, Note::G4]
+        );
+        assert_eq!(
+            Slash(Chord::Major(Note::C3), Note::G1).notes(),
+            vec![Note::G1, Note::C3, Note::E3, Note::G3]
+        );
+        assert_eq!(
+            Slash(Chord::Major(Note::Cminus1), Note::G1).notes(),
+            vec![Note::Gminus1, Note::C0, Note::E0, Note::G0]
+        );
+    }
+
+    #[test]
+    fn test_root_note() {
+        assert_eq!(
+            Slash(Chord::Major(Note::C3), Note::E3).root_note(),
+            Note::C3
+        );
+    }
+
+    #[test]
+    fn test_inversion() {
+        assert_eq!(
+            Slash(Inversion(Chord::Major(Note::C3)), Note::G2).notes(),
+            vec![Note::G2, Note::E3, Note::G3, Note::C4]
+        );
+        assert_eq!(
+            Inversion(Slash(Chord::Major(Note::C3), Note::D2)).notes(),
+            vec![Note::D2, Note::E3, Note::G3, Note::C4]
+        );
+        assert_eq!(
+            Inversion(Inversion(Slash(Chord::Major7th(Note::C3), Note::A2))).notes(),
+            vec![Note::A2, Note::G3, Note::B3, Note::C4, Note::E4]
+        );
+    }
+
+    #[test]
+    fn test_arpeggiator() {
+        let mut arp = UpArpeggiator::new(Slash(Chord::Major(Note::C3), Note::E2).notes());
+        assert_eq!(arp.next(), Some(Note::E2));
+        assert_eq!(arp.next(), Some(Note::C3));
+        assert_eq!(arp.next(), Some(Note::E3));
+        assert_eq!(arp.next(), Some(Note::G3));
+        assert_eq!(arp.next(), Some(Note::E2));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Slash(Chord::Major(Note::C3), Note::E3).to_string(), "C/E");
+        assert_eq!(
+            Slash(Chord::Minor7th(Note::A3), Note::G3).to_string(),
+            "Am7/G"
+        );
+    }
+
+    #[test]
+    fn test_spelled_display() {
+        let spelled = |chord: Chord, bass: Note| Spelled(&Slash(chord, bass)).to_string();
+        assert_eq!(
+            Slash(Chord::Minor7th(Note::Asharp3), Note::Gsharp2).to_string(),
+            "A#m7/G#"
+        );
+        assert_eq!(
+            spelled(Chord::Minor7th(Note::Asharp3), Note::Gsharp2),
+            "Bbm7/Ab"
+        );
+        assert_eq!(
+            spelled(Chord::Dominant7th(Note::E3), Note::Gsharp2),
+            "E7/G#"
+        );
+        assert_eq!(spelled(Chord::Major(Note::Dsharp3), Note::Csharp2), "Eb/Db");
+        assert_eq!(spelled(Chord::Major(Note::D3), Note::Asharp2), "D/A#");
+        assert_eq!(spelled(Chord::Major(Note::C3), Note::Asharp2), "C/Bb");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("C/E".parse(), Ok(Slash(Chord::Major(Note::C4), Note::E4)));
+        assert_eq!(
+            "Am7/G".parse(),
+            Ok(Slash(Chord::Minor7th(Note::A4), Note::G4))
+        );
+        assert_eq!(
+            Slash::parse("D/F#", 2),
+            Ok(Slash(Chord::Major(Note::D2), Note::Fsharp2))
+        );
+        assert_eq!(
+            Slash::parse("C/E", 3).unwrap().notes(),
+            vec![Note::E2, Note::C3, Note::E3, Note::G3]
+        );
+    }
+
+    #[test]
+    fn test_from_str_errors() {
+        assert_eq!(
+            "C".parse::<Slash<Chord>>().unwrap_err().kind(),
+            &ParseErrorKind::InvalidBass
+        );
+        assert_eq!(
+            "C/H".parse::<Slash<Chord>>().unwrap_err(),
+            ParseError::new(ParseErrorKind::InvalidBass, "invalid bass: H")
+        );
+        assert_eq!(
+            "C/Em".parse::<Slash<Chord>>().unwrap_err(),
+            ParseError::new(ParseErrorKind::InvalidBass, "invalid bass: Em")
+        );
+        assert_eq!(
+            "Cx/E".parse::<Slash<Chord>>().unwrap_err().kind(),
+            &ParseErrorKind::InvalidQuality
+        );
+    }
+}
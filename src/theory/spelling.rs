@@ -0,0 +1,523 @@
+use std::{fmt::Display, str::FromStr};
+
+use super::{Interval, Note, ParseError, ParseErrorKind, PitchClass};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Letter {
+    C,
+    D,
+    E,
+    F,
+    G,
+    A,
+    B,
+}
+
+impl Letter {
+    pub const ALL: [Letter; 7] = [
+        Letter::C,
+        Letter::D,
+        Letter::E,
+        Letter::F,
+        Letter::G,
+        Letter::A,
+        Letter::B,
+    ];
+
+    pub fn index(&self) -> u8 {
+        match self {
+            Letter::C => 0,
+            Letter::D => 1,
+            Letter::E => 2,
+            Letter::F => 3,
+            Letter::G => 4,
+            Letter::A => 5,
+            Letter::B => 6,
+        }
+    }
+
+    #[inline]
+    pub fn from_index(index: u8) -> Letter {
+        Self::ALL[index as usize % Self::ALL.len()].clone()
+    }
+
+    pub fn semitones(&self) -> u8 {
+        match self {
+            Letter::C => 0,
+            Letter::D => 2,
+            Letter::E => 4,
+            Letter::F => 5,
+            Letter::G => 7,
+            Letter::A => 9,
+            Letter::B => 11,
+        }
+    }
+
+    fn parse(c: char) -> Option<Letter> {
+        match c {
+            'C' | 'c' => Some(Letter::C),
+            'D' | 'd' => Some(Letter::D),
+            'E' | 'e' => Some(Letter::E),
+            'F' | 'f' => Some(Letter::F),
+            'G' | 'g' => Some(Letter::G),
+            'A' | 'a' => Some(Letter::A),
+            'B' | 'b' => Some(Letter::B),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Letter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Letter::C => "C",
+            Letter::D => "D",
+            Letter::E => "E",
+            Letter::F => "F",
+            Letter::G => "G",
+            Letter::A => "A",
+            Letter::B => "B",
+        };
+        write!(f, "{s}")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Accidental {
+    DoubleFlat,
+    Flat,
+    Natural,
+    Sharp,
+    DoubleSharp,
+}
+
+impl Accidental {
+    pub fn from_offset(offset: i8) -> Option<Accidental> {
+        match offset {
+            -2 => Some(Accidental::DoubleFlat),
+            -1 => Some(Accidental::Flat),
+            0 => Some(Accidental::Natural),
+            1 => Some(Accidental::Sharp),
+            2 => Some(Accidental::DoubleSharp),
+            _ => None,
+        }
+    }
+
+    pub fn offset(&self) -> i8 {
+        match self {
+            Accidental::DoubleFlat => -2,
+            Accidental::Flat => -1,
+            Accidental::Natural => 0,
+            Accidental::Sharp => 1,
+            Accidental::DoubleSharp => 2,
+        }
+    }
+
+    fn parse(s: &str) -> (Accidental, &str) {
+        for (prefix, accidental) in [
+            ("##", Accidental::DoubleSharp),
+            ("♯♯", Accidental::DoubleSharp),
+            ("x", Accidental::DoubleSharp),
+            ("𝄪", Accidental::DoubleSharp),
+            ("bb", Accidental::DoubleFlat),
+            ("♭♭", Accidental::DoubleFlat),
+            ("𝄫", Accidental::DoubleFlat),
+            ("#", Accidental::Sharp),
+            ("♯", Accidental::Sharp),
+            ("b", Accidental::Flat),
+            ("♭", Accidental::Flat),
+        ] {
+            if let Some(rest) = s.strip_prefix(prefix) {
+                return (accidental, rest);
+            }
+        }
+        (Accidental::Natural, s)
+    }
+}
+
+impl Display for Accidental {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Accidental::DoubleFlat => "bb",
+            Accidental::Flat => "b",
+            Accidental::Natural => "",
+            Accidental::Sharp => "#",
+            Accidental::DoubleSharp => "##",
+        };
+        write!(f, "{s}")
+    }
+}
+
+// NOTE: Displays chords with spelled pitches, while their own Display keeps the sharps of PitchClass
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Spelled<'a, T>(pub &'a T);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SpelledPitchClass {
+    letter: Letter,
+    accidental: Accidental,
+}
+
+impl SpelledPitchClass {
+    const SEMITONES: i16 = 12;
+
+    pub fn new(letter: Letter, accidental: Accidental) -> Self {
+        Self { letter, accidental }
+    }
+
+    pub fn sharp(pitch_class: &PitchClass) -> Self {
+        Self::with_direction(pitch_class, Accidental::Sharp)
+    }
+
+    pub fn flat(pitch_class: &PitchClass) -> Self {
+        Self::with_direction(pitch_class, Accidental::Flat)
+    }
+
+    fn with_direction(pitch_class: &PitchClass, accidental: Accidental) -> Self {
+        let semitones = pitch_class.semitones();
+        let natural = Letter::ALL
+            .into_iter()
+            .find(|letter| letter.semitones() == semitones);
+        match natural {
+            Some(letter) => Self::new(letter, Accidental::Natural),
+            None => {
+                let semitones = (semitones as i16 - accidental.offset() as i16)
+                    .rem_euclid(Self::SEMITONES) as u8;
+                let letter = Letter::ALL
+                    .into_iter()
+                    .find(|letter| letter.semitones() == semitones)
+                    .expect("should be natural next to accidental");
+                Self::new(letter, accidental)
+            }
+        }
+    }
+
+    // NOTE: Picks the spelling whose spelled tones need the fewest accidentals,
+    // preferring Db, Eb, F#, Ab and Bb on ties
+    pub(crate) fn simplest<F>(pitch_class: &PitchClass, spell: F) -> Self
+    where
+        F: Fn(&SpelledPitchClass) -> Vec<Option<SpelledPitchClass>>,
+    {
+        let candidates = if pitch_class == &PitchClass::FSharp {
+            [Self::sharp(pitch_class), Self::flat(pitch_class)]
+        } else {
+            [Self::flat(pitch_class), Self::sharp(pitch_class)]
+        };
+        candidates
+            .into_iter()
+            .min_by_key(|candidate| {
+                spell(candidate)
+                    .iter()
+                    .map(|tone| match tone {
+                        Some(tone) => tone.accidental().offset().unsigned_abs() as usize,
+                        None => usize::MAX / 16,
+                    })
+                    .sum::<usize>()
+            })
+            .expect("should have candidates")
+    }
+
+    #[inline]
+    pub fn letter(&self) -> &Letter {
+        &self.letter
+    }
+
+    #[inline]
+    pub fn accidental(&self) -> &Accidental {
+        &self.accidental
+    }
+
+    pub fn pitch_class(&self) -> PitchClass {
+        let semitones = (self.letter.semitones() as i16 + self.accidental.offset() as i16)
+            .rem_euclid(Self::SEMITONES);
+        PitchClass::from_semitones(semitones as u8)
+    }
+
+    pub fn transpose(&self, interval: &Interval) -> Option<SpelledPitchClass> {
+        let letter = Letter::from_index(self.letter.index() + interval.degree() - 1);
+        let semitones = self.pitch_class().semitones() as i16 + interval.semitones() as i16;
+        let offset = (semitones - letter.semitones() as i16 + Self::SEMITONES / 2)
+            .rem_euclid(Self::SEMITONES)
+            - Self::SEMITONES / 2;
+        Some(Self::new(letter, Accidental::from_offset(offset as i8)?))
+    }
+
+    pub(crate) fn parse_prefix(s: &str) -> Option<(SpelledPitchClass, &str)> {
+        let mut chars = s.chars();
+        let letter = Letter::parse(chars.next()?)?;
+        let (accidental, rest) = Accidental::parse(chars.as_str());
+        Some((Self::new(letter, accidental), rest))
+    }
+}
+
+impl FromStr for SpelledPitchClass {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseError::new(
+                ParseErrorKind::Empty,
+                "pitch class is empty",
+            ));
+        }
+        match Self::parse_prefix(s) {
+            Some((pitch_class, "")) => Ok(pitch_class),
+            _ => Err(ParseError::new(
+                ParseErrorKind::InvalidNote,
+                format!("invalid pitch class: {s}"),
+            )),
+        }
+    }
+}
+
+impl From<&PitchClass> for SpelledPitchClass {
+    #[inline]
+    fn from(value: &PitchClass) -> Self {
+        Self::sharp(value)
+    }
+}
+
+impl Display for SpelledPitchClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.letter, self.accidental)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SpelledNote {
+    pitch_class: SpelledPitchClass,
+    octave: i8,
+}
+
+impl SpelledNote {
+    const SEMITONES: i16 = 12;
+
+    pub fn new(pitch_class: SpelledPitchClass, octave: i8) -> Self {
+        Self {
+            pitch_class,
+            octave,
+        }
+    }
+
+    pub fn from_note(note: &Note, pitch_class: &SpelledPitchClass) -> Option<SpelledNote> {
+        if pitch_class.pitch_class() != note.pitch_class() {
+            return None;
+        }
+        let letter_number = note.note_number() as i16 - pitch_class.accidental().offset() as i16;
+        let octave = letter_number.div_euclid(Self::SEMITONES) - 1;
+        Some(Self::new(pitch_class.clone(), octave as i8))
+    }
+
+    #[inline]
+    pub fn pitch_class(&self) -> &SpelledPitchClass {
+        &self.pitch_class
+    }
+
+    #[inline]
+    pub fn octave(&self) -> i8 {
+        self.octave
+    }
+
+    // NOTE: Counts letters from C-1, ignoring accidentals
+    pub(crate) fn letter_number(&self) -> i16 {
+        (self.octave as i16 + 1) * Letter::ALL.len() as i16
+            + self.pitch_class.letter().index() as i16
+    }
+
+    pub(crate) fn note_number(&self) -> i16 {
+        (self.octave as i16 + 1) * Self::SEMITONES
+            + self.pitch_class.letter().semitones() as i16
+            + self.pitch_class.accidental().offset() as i16
+    }
+
+    pub fn note(&self) -> Option<Note> {
+        let note_number = self.note_number();
+        let range = Note::MIN_NOTE_NUMBER as i16..=Note::MAX_NOTE_NUMBER as i16;
+        if range.contains(&note_number) {
+            Note::from_note_number(note_number as u8)
+        } else {
+            None
+        }
+    }
+
+    pub fn transpose(&self, interval: &Interval) -> Option<SpelledNote> {
+        let pitch_class = self.pitch_class.transpose(interval)?;
+        let letter_number = self.note_number() + interval.semitones() as i16
+            - pitch_class.accidental().offset() as i16;
+        let octave = letter_number.div_euclid(Self::SEMITONES) - 1;
+        Some(Self::new(pitch_class, i8::try_from(octave).ok()?))
+    }
+}
+
+impl FromStr for SpelledNote {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseError::new(ParseErrorKind::Empty, "note is empty"));
+        }
+        let (pitch_class, octave) = SpelledPitchClass::parse_prefix(s).ok_or_else(|| {
+            ParseError::new(ParseErrorKind::InvalidNote, format!("invalid note: {s}"))
+        })?;
+        let octave: i8 = octave.parse().map_err(|_| {
+            ParseError::new(ParseErrorKind::InvalidNote, format!("invalid octave: {s}"))
+        })?;
+        let note = Self::new(pitch_class, octave);
+        if note.note().is_none() {
+            return Err(ParseError::new(
+                ParseErrorKind::OutOfRange,
+                format!("note is out of range: {s}"),
+            ));
+        }
+        Ok(note)
+    }
+}
+
+impl From<&Note> for SpelledNote {
+    fn from(value: &Note) -> Self {
+        Self::from_note(value, &SpelledPitchClass::sharp(&value.pitch_class()))
+            .expect("should be spelled with its own pitch class")
+    }
+}
+
+impl Display for SpelledNote {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.pitch_class, self.octave)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spelled_pitch_class() {
+        let b_flat = SpelledPitchClass::new(Letter::B, Accidental::Flat);
+        assert_eq!(b_flat.pitch_class(), PitchClass::ASharp);
+        assert_eq!(b_flat.to_string(), "Bb");
+        assert_eq!(
+            SpelledPitchClass::new(Letter::B, Accidental::Sharp).pitch_class(),
+            PitchClass::C
+        );
+        assert_eq!(
+            SpelledPitchClass::new(Letter::C, Accidental::DoubleFlat).pitch_class(),
+            PitchClass::ASharp
+        );
+
+        assert_eq!(
+            SpelledPitchClass::flat(&PitchClass::GSharp).to_string(),
+            "Ab"
+        );
+        assert_eq!(
+            SpelledPitchClass::sharp(&PitchClass::GSharp).to_string(),
+            "G#"
+        );
+        assert_eq!(SpelledPitchClass::flat(&PitchClass::E).to_string(), "E");
+    }
+
+    #[test]
+    fn test_spelled_pitch_class_transpose() {
+        let f = SpelledPitchClass::new(Letter::F, Accidental::Natural);
+        assert_eq!(
+            f.transpose(&Interval::Min3),
+            Some(SpelledPitchClass::new(Letter::A, Accidental::Flat))
+        );
+        let c = SpelledPitchClass::new(Letter::C, Accidental::Natural);
+        assert_eq!(
+            c.transpose(&Interval::D7),
+            Some(SpelledPitchClass::new(Letter::B, Accidental::DoubleFlat))
+        );
+        assert_eq!(
+            c.transpose(&Interval::A5),
+            Some(SpelledPitchClass::new(Letter::G, Accidental::Sharp))
+        );
+        let b_flat = SpelledPitchClass::new(Letter::B, Accidental::Flat);
+        assert_eq!(
+            b_flat.transpose(&Interval::Maj9),
+            Some(SpelledPitchClass::new(Letter::C, Accidental::Natural))
+        );
+        let f_double_flat = SpelledPitchClass::new(Letter::F, Accidental::DoubleFlat);
+        assert_eq!(f_double_flat.transpose(&Interval::D5), None);
+    }
+
+    #[test]
+    fn test_spelled_note() {
+        assert_eq!(
+            "Bb3".parse::<SpelledNote>().unwrap().note(),
+            Some(Note::Asharp3)
+        );
+        assert_eq!(
+            "C##4".parse::<SpelledNote>().unwrap().note(),
+            Some(Note::D4)
+        );
+        assert_eq!("Fb2".parse::<SpelledNote>().unwrap().note(), Some(Note::E2));
+        assert_eq!("Cb4".parse::<SpelledNote>().unwrap().note(), Some(Note::B3));
+        assert_eq!("B#3".parse::<SpelledNote>().unwrap().note(), Some(Note::C4));
+        assert_eq!(
+            "Ebb4".parse::<SpelledNote>().unwrap().note(),
+            Some(Note::D4)
+        );
+        assert_eq!(
+            "A-1".parse::<SpelledNote>().unwrap().note(),
+            Some(Note::Aminus1)
+        );
+        assert_eq!("G♭5".parse::<SpelledNote>().unwrap().to_string(), "Gb5");
+
+        assert_eq!(
+            "".parse::<SpelledNote>().unwrap_err().kind(),
+            &ParseErrorKind::Empty
+        );
+        assert_eq!(
+            "H4".parse::<SpelledNote>().unwrap_err().kind(),
+            &ParseErrorKind::InvalidNote
+        );
+        assert_eq!(
+            "C".parse::<SpelledNote>().unwrap_err().kind(),
+            &ParseErrorKind::InvalidNote
+        );
+        assert_eq!(
+            "Cb-1".parse::<SpelledNote>().unwrap_err().kind(),
+            &ParseErrorKind::OutOfRange
+        );
+        assert_eq!(
+            "G#9".parse::<SpelledNote>().unwrap_err().kind(),
+            &ParseErrorKind::OutOfRange
+        );
+    }
+
+    #[test]
+    fn test_spelled_note_from_note() {
+        let b_sharp = SpelledPitchClass::new(Letter::B, Accidental::Sharp);
+        assert_eq!(
+            SpelledNote::from_note(&Note::C4, &b_sharp).map(|note| note.to_string()),
+            Some("B#3".to_string())
+        );
+        let c_flat = SpelledPitchClass::new(Letter::C, Accidental::Flat);
+        assert_eq!(
+            SpelledNote::from_note(&Note::B3, &c_flat).map(|note| note.to_string()),
+            Some("Cb4".to_string())
+        );
+        assert_eq!(SpelledNote::from_note(&Note::C4, &c_flat), None);
+        assert_eq!(SpelledNote::from(&Note::Asharp3).to_string(), "A#3");
+    }
+
+    #[test]
+    fn test_spelled_note_transpose() {
+        let note: SpelledNote = "B3".parse().unwrap();
+        assert_eq!(
+            note.transpose(&Interval::Min2).map(|note| note.to_string()),
+            Some("C4".to_string())
+        );
+        assert_eq!(
+            note.transpose(&Interval::D2).map(|note| note.to_string()),
+            Some("Cb4".to_string())
+        );
+        let note: SpelledNote = "Bb3".parse().unwrap();
+        assert_eq!(
+            note.transpose(&Interval::Maj9).map(|note| note.to_string()),
+            Some("C5".to_string())
+        );
+    }
+}
@@ -0,0 +1,293 @@
+use std::{fmt::Display, str::FromStr};
+
+use super::{Duration, NoteValue, ParseError, ParseErrorKind};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Accent {
+    Strong,
+    Medium,
+    Weak,
+    Offbeat,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeSignature {
+    beats: u8,
+    unit: Duration,
+    groups: Vec<u8>,
+}
+
+impl TimeSignature {
+    const COMPOUND_GROUP: u8 = 3;
+    const SIMPLE_GROUP: u8 = 2;
+
+    pub fn new(beats: u8, unit: Duration) -> Option<Self> {
+        let groups = Self::default_groups(beats, &unit)?;
+        Some(Self {
+            beats,
+            unit,
+            groups,
+        })
+    }
+
+    // NOTE: Groups are counted in units and must add up to the beats, e.g. 3+3+2 for 8/8
+    pub fn with_groups(beats: u8, unit: Duration, groups: Vec<u8>) -> Option<Self> {
+        if beats == 0
+            || groups.contains(&0)
+            || groups.iter().map(|group| *group as u16).sum::<u16>() != beats as u16
+        {
+            return None;
+        }
+        Some(Self {
+            beats,
+            unit,
+            groups,
+        })
+    }
+
+    fn default_groups(beats: u8, unit: &Duration) -> Option<Vec<u8>> {
+        if beats == 0 {
+            return None;
+        }
+
+        let groups = if Self::is_compound_meter(beats, unit) {
+            vec![Self::COMPOUND_GROUP; (beats / Self::COMPOUND_GROUP) as usize]
+        } else if beats <= Self::COMPOUND_GROUP {
+            vec![beats]
+        } else if beats.is_multiple_of(Self::SIMPLE_GROUP) {
+            vec![Self::SIMPLE_GROUP; (beats / Self::SIMPLE_GROUP) as usize]
+        } else {
+            // NOTE: Odd meters end with a longer group, e.g. 2+3 for 5/4 and 2+2+3 for 7/8
+            let mut groups = vec![
+                Self::SIMPLE_GROUP;
+                ((beats - Self::COMPOUND_GROUP) / Self::SIMPLE_GROUP) as usize
+            ];
+            groups.push(Self::COMPOUND_GROUP);
+            groups
+        };
+        Some(groups)
+    }
+
+    fn is_compound_meter(beats: u8, unit: &Duration) -> bool {
+        beats > Self::COMPOUND_GROUP
+            && beats.is_multiple_of(Self::COMPOUND_GROUP)
+            && unit.note_value() <= Duration::Eighth.note_value()
+    }
+
+    #[inline]
+    pub fn beats(&self) -> u8 {
+        self.beats
+    }
+
+    #[inline]
+    pub fn unit(&self) -> &Duration {
+        &self.unit
+    }
+
+    #[inline]
+    pub fn groups(&self) -> &[u8] {
+        &self.groups
+    }
+
+    #[inline]
+    pub fn is_compound(&self) -> bool {
+        Self::is_compound_meter(self.beats, &self.unit)
+    }
+
+    #[inline]
+    pub fn measure(&self) -> NoteValue {
+        self.unit.note_value() * self.beats as u64
+    }
+
+    // NOTE: The last step is shorter when the measure is not a multiple of the step, e.g. 7/8 in quarters
+    pub fn steps(&self, step: &NoteValue) -> usize {
+        self.measure()
+            .checked_div(step)
+            .expect("should have non-zero step")
+            .ceil() as usize
+    }
+
+    pub fn accent(&self, position: &NoteValue) -> Accent {
+        if *position == NoteValue::ZERO {
+            return Accent::Strong;
+        }
+
+        let units = position
+            .checked_div(&self.unit.note_value())
+            .expect("should have non-zero unit");
+        if !units.is_integer() {
+            return Accent::Offbeat;
+        }
+
+        let mut start = 0;
+        for group in &self.groups {
+            if units.numerator() == start {
+                return Accent::Medium;
+            }
+            start += *group as u64;
+        }
+        Accent::Weak
+    }
+}
+
+impl Default for TimeSignature {
+    #[inline]
+    fn default() -> Self {
+        Self::new(4, Duration::Quarter).expect("should be common time")
+    }
+}
+
+impl FromStr for TimeSignature {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseError::new(
+                ParseErrorKind::Empty,
+                "time signature is empty",
+            ));
+        }
+
+        let invalid = || {
+            ParseError::new(
+                ParseErrorKind::InvalidTimeSignature,
+                format!("invalid time signature: {s}"),
+            )
+        };
+        let (beats, unit) = s.split_once('/').ok_or_else(invalid)?;
+        let beats: u8 = beats.trim().parse().map_err(|_| invalid())?;
+        let unit: u8 = unit.trim().parse().map_err(|_| invalid())?;
+        let unit = Duration::from_denominator(unit).ok_or_else(invalid)?;
+        Self::new(beats, unit).ok_or_else(invalid)
+    }
+}
+
+impl Display for TimeSignature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.beats, self.unit.beats_per_measure())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_groups() {
+        let groups = |s: &str| s.parse::<TimeSignature>().unwrap().groups().to_vec();
+        assert_eq!(groups("4/4"), vec![2, 2]);
+        assert_eq!(groups("3/4"), vec![3]);
+        assert_eq!(groups("2/4"), vec![2]);
+        assert_eq!(groups("5/4"), vec![2, 3]);
+        assert_eq!(groups("7/8"), vec![2, 2, 3]);
+        assert_eq!(groups("6/8"), vec![3, 3]);
+        assert_eq!(groups("9/8"), vec![3, 3, 3]);
+        assert_eq!(groups("12/8"), vec![3, 3, 3, 3]);
+        assert_eq!(groups("6/4"), vec![2, 2, 2]);
+
+        assert!(TimeSignature::with_groups(8, Duration::Eighth, vec![3, 3, 2]).is_some());
+        assert!(TimeSignature::with_groups(8, Duration::Eighth, vec![3, 3]).is_none());
+        assert!(TimeSignature::new(0, Duration::Quarter).is_none());
+    }
+
+    #[test]
+    fn test_compound() {
+        assert!("6/8".parse::<TimeSignature>().unwrap().is_compound());
+        assert!("12/8".parse::<TimeSignature>().unwrap().is_compound());
+        assert!(!"3/8".parse::<TimeSignature>().unwrap().is_compound());
+        assert!(!"6/4".parse::<TimeSignature>().unwrap().is_compound());
+    }
+
+    #[test]
+    fn test_steps() {
+        let signature: TimeSignature = "4/4".parse().unwrap();
+        assert_eq!(signature.steps(&Duration::Quarter.note_value()), 4);
+        assert_eq!(signature.steps(&Duration::Eighth.note_value()), 8);
+        let signature: TimeSignature = "6/8".parse().unwrap();
+        assert_eq!(signature.steps(&Duration::Eighth.note_value()), 6);
+        assert_eq!(signature.steps(&Duration::Quarter.note_value()), 3);
+        let signature: TimeSignature = "7/8".parse().unwrap();
+        assert_eq!(signature.steps(&Duration::Quarter.note_value()), 4);
+    }
+
+    #[test]
+    fn test_accent() {
+        let eighth = Duration::Eighth.note_value();
+        let accents = |signature: &TimeSignature, steps: u64| {
+            (0..steps)
+                .map(|step| signature.accent(&(eighth * step)))
+                .collect::<Vec<_>>()
+        };
+
+        let signature: TimeSignature = "6/8".parse().unwrap();
+        assert_eq!(
+            accents(&signature, 6),
+            vec![
+                Accent::Strong,
+                Accent::Weak,
+                Accent::Weak,
+                Accent::Medium,
+                Accent::Weak,
+                Accent::Weak
+            ]
+        );
+
+        let signature: TimeSignature = "7/8".parse().unwrap();
+        assert_eq!(
+            accents(&signature, 7),
+            vec![
+                Accent::Strong,
+                Accent::Weak,
+                Accent::Medium,
+                Accent::Weak,
+                Accent::Medium,
+                Accent::Weak,
+                Accent::Weak
+            ]
+        );
+
+        let signature: TimeSignature = "4/4".parse().unwrap();
+        assert_eq!(
+            accents(&signature, 4),
+            vec![
+                Accent::Strong,
+                Accent::Offbeat,
+                Accent::Weak,
+                Accent::Offbeat
+            ]
+        );
+        assert_eq!(
+            signature.accent(&Duration::Half.note_value()),
+            Accent::Medium
+        );
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            "3/4".parse(),
+            Ok(TimeSignature::new(3, Duration::Quarter).unwrap())
+        );
+        assert_eq!(
+            "12 / 8".parse::<TimeSignature>().unwrap().to_string(),
+            "12/8"
+        );
+        assert_eq!(
+            "".parse::<TimeSignature>().unwrap_err().kind(),
+            &ParseErrorKind::Empty
+        );
+        assert_eq!(
+            "7/3".parse::<TimeSignature>().unwrap_err().kind(),
+            &ParseErrorKind::InvalidTimeSignature
+        );
+        assert_eq!(
+            "0/4".parse::<TimeSignature>().unwrap_err().kind(),
+            &ParseErrorKind::InvalidTimeSignature
+        );
+        assert_eq!(
+            "4".parse::<TimeSignature>().unwrap_err().kind(),
+            &ParseErrorKind::InvalidTimeSignature
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use crate::{line_error::LineError, unit::Frequency};
+
+use super::{KeyboardMapping, Note, PitchClass, ScalaScale};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TuningSystem {
+    EqualTemperament(u8),
+    JustIntonation(PitchClass),
+    Pythagorean(PitchClass),
+    Meantone(PitchClass),
+    Scala(ScalaScale, KeyboardMapping),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tuning {
+    reference: Frequency,
+    system: TuningSystem,
+}
+
+impl Tuning {
+    const SEMITONES: u8 = 12;
+    const JUST_RATIOS: [f32; 12] = [
+        1.0,
+        16.0 / 15.0,
+        9.0 / 8.0,
+        6.0 / 5.0,
+        5.0 / 4.0,
+        4.0 / 3.0,
+        45.0 / 32.0,
+        3.0 / 2.0,
+        8.0 / 5.0,
+        5.0 / 3.0,
+        9.0 / 5.0,
+        15.0 / 8.0,
+    ];
+
+    pub fn new<F: Into<Frequency>>(reference: F, system: TuningSystem) -> Self {
+        Self {
+            reference: reference.into(),
+            system,
+        }
+    }
+
+    pub fn scala(scale: ScalaScale, mapping: KeyboardMapping) -> Self {
+        let reference = mapping.reference_freq() as f32;
+        Self::new(reference, TuningSystem::Scala(scale, mapping))
+    }
+
+    pub fn from_scala_bytes(scl: &[u8], kbm: Option<&[u8]>) -> Result<Self, LineError> {
+        let scale = ScalaScale::from_bytes(scl)?;
+        let mapping = match kbm {
+            Some(kbm) => KeyboardMapping::from_bytes(kbm)?,
+            None => KeyboardMapping::default(),
+        };
+        Ok(Self::scala(scale, mapping))
+    }
+
+    #[inline]
+    pub fn reference(&self) -> Frequency {
+        self.reference
+    }
+
+    #[inline]
+    pub fn system(&self) -> &TuningSystem {
+        &self.system
+    }
+
+    pub fn freq(&self, note: &Note) -> Frequency {
+        match &self.system {
+            TuningSystem::EqualTemperament(divisions) => {
+                self.equal_tempered_freq(note.note_number(), *divisions)
+            }
+            TuningSystem::JustIntonation(tonic) => {
+                self.tonic_relative_freq(note, tonic, |degree| Self::JUST_RATIOS[degree as usize])
+            }
+            // NOTE: Pythagorean spans -5..=6 fifths around the tonic, e.g. Db to F# for C
+            TuningSystem::Pythagorean(tonic) => {
+                self.tonic_relative_freq(note, tonic, |degree| Self::fifths_ratio(degree, -5, 1.5))
+            }
+            // NOTE: Quarter-comma meantone spans -3..=8 fifths around the tonic, e.g. Eb to G# for C
+            TuningSystem::Meantone(tonic) => self.tonic_relative_freq(note, tonic, |degree| {
+                Self::fifths_ratio(degree, -3, 5.0_f32.powf(0.25))
+            }),
+            TuningSystem::Scala(scale, mapping) => self.scala_freq(note, scale, mapping),
+        }
+    }
+
+    fn scala_freq(&self, note: &Note, scale: &ScalaScale, mapping: &KeyboardMapping) -> Frequency {
+        let reference_note = mapping.reference_note();
+        let reference_ratio = mapping
+            .ratio(scale, reference_note)
+            .expect("reference note should be mapped");
+        match mapping.ratio(scale, note.note_number()) {
+            Some(ratio) => ((self.reference.0 as f64 * ratio / reference_ratio) as f32).into(),
+            // NOTE: Falls back to 12-TET around the reference note for unmapped keys
+            None => {
+                let relative_note_number = note.note_number() as i16 - reference_note as i16;
+                (self.reference.0
+                    * (2.0_f32).powf(relative_note_number as f32 / Self::SEMITONES as f32))
+                .into()
+            }
+        }
+    }
+
+    fn equal_tempered_freq(&self, note_number: u8, divisions: u8) -> Frequency {
+        let relative_note_number = note_number as i16 - Note::A4.note_number() as i16;
+        let divisions = divisions.max(1);
+        (self.reference.0 * (2.0_f32).powf(relative_note_number as f32 / divisions as f32)).into()
+    }
+
+    // NOTE: Anchored so that A4 sounds at the reference pitch whatever the tonic
+    fn tonic_relative_freq<F>(&self, note: &Note, tonic: &PitchClass, ratio: F) -> Frequency
+    where
+        F: Fn(u8) -> f32,
+    {
+        let relative_ratio = |note_number: u8| {
+            let degree =
+                (note_number as i16 - tonic.semitones() as i16).rem_euclid(Self::SEMITONES as i16);
+            let tonic_note_number = note_number as i16 - degree;
+            let relative_note_number = tonic_note_number - Note::A4.note_number() as i16;
+            (2.0_f32).powf(relative_note_number as f32 / Self::SEMITONES as f32)
+                * ratio(degree as u8)
+        };
+        (self.reference.0 * relative_ratio(note.note_number())
+            / relative_ratio(Note::A4.note_number()))
+        .into()
+    }
+
+    fn fifths_ratio(degree: u8, lowest_fifth: i8, fifth: f32) -> f32 {
+        let fifths = (lowest_fifth..lowest_fifth + Self::SEMITONES as i8)
+            .find(|fifths| (*fifths as i16 * 7).rem_euclid(Self::SEMITONES as i16) == degree as i16)
+            .expect("should be found in the chain of fifths");
+        let ratio = fifth.powi(fifths as i32);
+        let octaves = ratio.log2().floor();
+        ratio / (2.0_f32).powf(octaves)
+    }
+}
+
+impl Default for Tuning {
+    fn default() -> Self {
+        Self::new(440.0, TuningSystem::EqualTemperament(Self::SEMITONES))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_freq_eq(actual: Frequency, expected: f32) {
+        assert!(
+            (actual.0 - expected).abs() < 0.01,
+            "expected {expected}, got {}",
+            actual.0
+        );
+    }
+
+    #[test]
+    fn test_default() {
+        let tuning = Tuning::default();
+        assert_eq!(tuning.freq(&Note::A4), Frequency(440.0));
+        assert_eq!(tuning.freq(&Note::C4), Frequency(261.62555));
+    }
+
+    #[test]
+    fn test_reference() {
+        let tuning = Tuning::new(442.0, TuningSystem::EqualTemperament(12));
+        assert_eq!(tuning.freq(&Note::A4), Frequency(442.0));
+        assert_eq!(tuning.freq(&Note::A3), Frequency(221.0));
+        assert_eq!(tuning.freq(&Note::A5), Frequency(884.0));
+    }
+
+    #[test]
+    fn test_equal_temperament_divisions() {
+        let tuning = Tuning::new(440.0, TuningSystem::EqualTemperament(24));
+        assert_eq!(tuning.freq(&Note::A4), Frequency(440.0));
+        assert_freq_eq(tuning.freq(&Note::Asharp4), 452.893);
+        assert_freq_eq(tuning.freq(&Note::A5), 622.254);
+        assert_eq!(tuning.freq(&Note::A6), Frequency(880.0));
+    }
+
+    #[test]
+    fn test_just_intonation() {
+        let tuning = Tuning::new(440.0, TuningSystem::JustIntonation(PitchClass::C));
+        assert_freq_eq(tuning.freq(&Note::C4), 264.0);
+        assert_freq_eq(tuning.freq(&Note::E4), 330.0);
+        assert_freq_eq(tuning.freq(&Note::G4), 396.0);
+        assert_freq_eq(tuning.freq(&Note::A4), 440.0);
+        assert_freq_eq(tuning.freq(&Note::C5), 528.0);
+        assert_freq_eq(tuning.freq(&Note::B3), 247.5);
+    }
+
+    #[test]
+    fn test_just_intonation_tonic() {
+        let tuning = Tuning::new(440.0, TuningSystem::JustIntonation(PitchClass::A));
+        assert_eq!(tuning.freq(&Note::A4), Frequency(440.0));
+        assert_freq_eq(tuning.freq(&Note::Csharp5), 550.0);
+        assert_freq_eq(tuning.freq(&Note::E5), 660.0);
+        assert_freq_eq(tuning.freq(&Note::E4), 330.0);
+    }
+
+    #[test]
+    fn test_pythagorean() {
+        let tuning = Tuning::new(440.0, TuningSystem::Pythagorean(PitchClass::A));
+        assert_eq!(tuning.freq(&Note::A4), Frequency(440.0));
+        assert_freq_eq(tuning.freq(&Note::B4), 495.0);
+        assert_freq_eq(tuning.freq(&Note::Csharp5), 556.875);
+        assert_freq_eq(tuning.freq(&Note::D5), 586.667);
+        assert_freq_eq(tuning.freq(&Note::E5), 660.0);
+        assert_freq_eq(tuning.freq(&Note::Dsharp5), 626.484);
+    }
+
+    #[test]
+    fn test_meantone() {
+        let tuning = Tuning::new(440.0, TuningSystem::Meantone(PitchClass::A));
+        assert_eq!(tuning.freq(&Note::A4), Frequency(440.0));
+        assert_freq_eq(tuning.freq(&Note::Csharp5), 550.0);
+        assert_freq_eq(tuning.freq(&Note::E5), 657.953);
+        assert_freq_eq(tuning.freq(&Note::B4), 491.935);
+
+        let tuning = Tuning::new(440.0, TuningSystem::Meantone(PitchClass::C));
+        assert_freq_eq(tuning.freq(&Note::A4), 440.0);
+        assert_freq_eq(tuning.freq(&Note::C4), 263.181);
+    }
+
+    #[test]
+    fn test_scala() {
+        let scl = b"pythagorean\n12\n256/243\n9/8\n32/27\n81/64\n4/3\n729/512\n3/2\n128/81\n27/16\n16/9\n243/128\n2/1\n";
+        let tuning = Tuning::from_scala_bytes(scl, None).unwrap();
+        assert_eq!(tuning.reference(), Frequency(440.0));
+        assert_eq!(tuning.freq(&Note::A4), Frequency(440.0));
+        assert_eq!(tuning.freq(&Note::A5), Frequency(880.0));
+        assert_freq_eq(tuning.freq(&Note::C4), 260.741);
+        assert_freq_eq(tuning.freq(&Note::G4), 391.111);
+    }
+
+    #[test]
+    fn test_scala_unmapped() {
+        let scl = b"major\n7\n9/8\n5/4\n4/3\n3/2\n5/3\n15/8\n2/1\n";
+        let kbm = b"12\n0\n127\n60\n60\n261.6\n7\n0\nx\n1\nx\n2\n3\nx\n4\nx\n5\nx\n6\n";
+        let tuning = Tuning::from_scala_bytes(scl, Some(kbm)).unwrap();
+        assert_eq!(tuning.freq(&Note::C4), Frequency(261.6));
+        assert_freq_eq(tuning.freq(&Note::E4), 327.0);
+        assert_freq_eq(tuning.freq(&Note::C5), 523.2);
+        assert_freq_eq(tuning.freq(&Note::Csharp4), 277.156);
+    }
+
+    #[test]
+    fn test_scala_error() {
+        assert_eq!(
+            Tuning::from_scala_bytes(b"test\n1\nfoo\n", None).unwrap_err(),
+            LineError::new(3, "invalid pitch: foo")
+        );
+        assert_eq!(
+            Tuning::from_scala_bytes(
+                b"test\n1\n2/1\n",
+                Some(b"2\n0\n127\n60\n61\n440.0\n1\n0\nx\n")
+            )
+            .unwrap_err(),
+            LineError::new(5, "reference note should be mapped: 61")
+        );
+    }
+
+    #[test]
+    fn test_fifths_ratio() {
+        assert_eq!(Tuning::fifths_ratio(0, -5, 1.5), 1.0);
+        assert_eq!(Tuning::fifths_ratio(7, -5, 1.5), 1.5);
+        assert_eq!(Tuning::fifths_ratio(5, -5, 1.5), 4.0 / 3.0);
+        assert_eq!(Tuning::fifths_ratio(2, -5, 1.5), 9.0 / 8.0);
+    }
+}
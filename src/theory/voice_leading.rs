@@ -0,0 +1,345 @@
+use super::{ChordLike, Note, PitchClass};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Voicing {
+    notes: Vec<Note>,
+    root: Note,
+}
+
+impl Voicing {
+    pub fn new(notes: Vec<Note>, root: Note) -> Self {
+        Self { notes, root }
+    }
+}
+
+impl ChordLike for Voicing {
+    #[inline]
+    fn notes(&self) -> Vec<Note> {
+        self.notes.clone()
+    }
+
+    #[inline]
+    fn root_note(&self) -> Note {
+        self.root.clone()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoiceLeader {
+    low: Note,
+    high: Note,
+}
+
+impl VoiceLeader {
+    const SEMITONES: i16 = 12;
+    const PARALLEL_PENALTY: i32 = 100;
+
+    pub fn new(low: Note, high: Note) -> Self {
+        Self { low, high }
+    }
+
+    pub fn lead<T: ChordLike>(&self, chords: &[T]) -> Vec<Voicing> {
+        let candidates: Vec<Vec<Vec<u8>>> = chords
+            .iter()
+            .map(|chord| {
+                let voicings = self.voicings(chord);
+                if voicings.is_empty() {
+                    let mut notes: Vec<u8> = chord
+                        .notes()
+                        .iter()
+                        .map(|note| note.note_number())
+                        .collect();
+                    notes.sort();
+                    vec![notes]
+                } else {
+                    voicings
+                }
+            })
+            .collect();
+
+        let Some(first) = candidates.first() else {
+            return vec![];
+        };
+
+        // NOTE: Viterbi over candidate voicings, keeping (cost, previous index) per voicing
+        let mut costs: Vec<Vec<(i32, usize)>> =
+            vec![first.iter().map(|v| (self.center_cost(v), 0)).collect()];
+        for i in 1..candidates.len() {
+            let step = candidates[i]
+                .iter()
+                .map(|current| {
+                    candidates[i - 1]
+                        .iter()
+                        .zip(&costs[i - 1])
+                        .enumerate()
+                        .map(|(j, (previous, (cost, _)))| {
+                            (cost + Self::transition_cost(previous, current), j)
+                        })
+                        .min()
+                        .expect("should have previous voicings")
+                })
+                .collect();
+            costs.push(step);
+        }
+
+        let mut index = costs
+            .last()
+            .expect("should have costs")
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, (cost, _))| *cost)
+            .map(|(i, _)| i)
+            .expect("should have voicings");
+        let mut path = vec![index; candidates.len()];
+        for i in (1..candidates.len()).rev() {
+            path[i] = index;
+            index = costs[i][index].1;
+        }
+        path[0] = index;
+
+        chords
+            .iter()
+            .zip(path)
+            .zip(&candidates)
+            .map(|((chord, index), voicings)| {
+                let notes = voicings[index]
+                    .iter()
+                    .filter_map(|note_number| Note::from_note_number(*note_number))
+                    .collect();
+                Voicing::new(notes, chord.root_note())
+            })
+            .collect()
+    }
+
+    pub fn voicings<T: ChordLike>(&self, chord: &T) -> Vec<Vec<u8>> {
+        let mut pitch_classes: Vec<PitchClass> = vec![];
+        for note in chord.notes() {
+            let pitch_class = note.pitch_class();
+            if !pitch_classes.contains(&pitch_class) {
+                pitch_classes.push(pitch_class);
+            }
+        }
+
+        let mut voicings: Vec<Vec<u8>> = vec![];
+        for rotation in 0..pitch_classes.len() {
+            let mut order = pitch_classes.clone();
+            order.rotate_left(rotation);
+
+            for bass in self.low.note_number()..=self.high.note_number() {
+                if bass % Self::SEMITONES as u8 != order[0].semitones() {
+                    continue;
+                }
+
+                let Some(close) = Self::close_voicing(bass, &order) else {
+                    continue;
+                };
+                let mut variants = vec![Some(close.clone())];
+                if close.len() >= 4 {
+                    variants.push(Self::drop(&close, 2));
+                    variants.push(Self::drop(&close, 3));
+                }
+
+                for voicing in variants.into_iter().flatten() {
+                    let in_range = voicing.iter().all(|note| {
+                        (self.low.note_number()..=self.high.note_number()).contains(note)
+                    });
+                    if in_range && !voicings.contains(&voicing) {
+                        voicings.push(voicing);
+                    }
+                }
+            }
+        }
+        voicings
+    }
+
+    fn close_voicing(bass: u8, order: &[PitchClass]) -> Option<Vec<u8>> {
+        let mut notes = vec![bass];
+        for pitch_class in &order[1..] {
+            let previous = *notes.last().expect("should have bass") as i16;
+            let offset = (pitch_class.semitones() as i16 - previous).rem_euclid(Self::SEMITONES);
+            let offset = if offset == 0 { Self::SEMITONES } else { offset };
+            notes.push(u8::try_from(previous + offset).ok()?);
+        }
+        Some(notes)
+    }
+
+    // NOTE: Drops the n-th voice from the top by an octave (e.g. drop-2, drop-3)
+    fn drop(close: &[u8], n: usize) -> Option<Vec<u8>> {
+        let mut notes = close.to_vec();
+        let index = notes.len().checked_sub(n)?;
+        notes[index] = notes[index].checked_sub(Self::SEMITONES as u8)?;
+        notes.sort();
+        Some(notes)
+    }
+
+    fn center_cost(&self, voicing: &[u8]) -> i32 {
+        let center = (self.low.note_number() as i32 + self.high.note_number() as i32) / 2;
+        let sum: i32 = voicing.iter().map(|note| *note as i32).sum();
+        (sum / voicing.len().max(1) as i32 - center).abs()
+    }
+
+    fn transition_cost(previous: &[u8], current: &[u8]) -> i32 {
+        let distance = |a: u8, b: u8| (a as i32 - b as i32).abs();
+
+        if previous.len() != current.len() {
+            let nearest = |from: &[u8], to: &[u8]| -> i32 {
+                from.iter()
+                    .map(|a| to.iter().map(|b| distance(*a, *b)).min().unwrap_or(0))
+                    .sum()
+            };
+            return nearest(previous, current) + nearest(current, previous);
+        }
+
+        let motion: i32 = previous
+            .iter()
+            .zip(current)
+            .map(|(a, b)| distance(*a, *b))
+            .sum();
+        motion + Self::parallels(previous, current) as i32 * Self::PARALLEL_PENALTY
+    }
+
+    fn parallels(previous: &[u8], current: &[u8]) -> usize {
+        let mut count = 0;
+        for i in 0..previous.len() {
+            for j in (i + 1)..previous.len() {
+                let before = previous[j] as i16 - previous[i] as i16;
+                let after = current[j] as i16 - current[i] as i16;
+                let is_perfect =
+                    |interval: i16| matches!(interval.rem_euclid(Self::SEMITONES), 0 | 7);
+                let motion_i = current[i] as i16 - previous[i] as i16;
+                let motion_j = current[j] as i16 - previous[j] as i16;
+                if is_perfect(before)
+                    && before.rem_euclid(Self::SEMITONES) == after.rem_euclid(Self::SEMITONES)
+                    && motion_i != 0
+                    && motion_i.signum() == motion_j.signum()
+                {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::theory::Chord;
+
+    use super::*;
+
+    fn note_numbers(voicing: &Voicing) -> Vec<u8> {
+        voicing
+            .notes()
+            .iter()
+            .map(|note| note.note_number())
+            .collect()
+    }
+
+    fn total_motion(voicings: &[Vec<u8>]) -> i32 {
+        voicings
+            .windows(2)
+            .map(|w| VoiceLeader::transition_cost(&w[0], &w[1]))
+            .sum()
+    }
+
+    #[test]
+    fn test_voicings() {
+        let leader = VoiceLeader::new(Note::C4, Note::C5);
+        assert_eq!(
+            leader.voicings(&Chord::Major(Note::C3)),
+            vec![vec![60, 64, 67], vec![64, 67, 72]]
+        );
+    }
+
+    #[test]
+    fn test_voicings_drop() {
+        let leader = VoiceLeader::new(Note::C3, Note::C5);
+        let voicings = leader.voicings(&Chord::Major7th(Note::C4));
+        // close C4 E4 G4 B4, drop-2 G3 C4 E4 B4, drop-3 E3 C4 G4 B4
+        assert!(voicings.contains(&vec![60, 64, 67, 71]));
+        assert!(voicings.contains(&vec![55, 60, 64, 71]));
+        assert!(voicings.contains(&vec![52, 60, 67, 71]));
+    }
+
+    #[test]
+    fn test_lead() {
+        let leader = VoiceLeader::new(Note::C4, Note::C5);
+        let voicings = leader.lead(&[
+            Chord::Major(Note::C3),
+            Chord::Major(Note::F3),
+            Chord::Major(Note::G3),
+            Chord::Major(Note::C3),
+        ]);
+        assert_eq!(
+            voicings.iter().map(note_numbers).collect::<Vec<_>>(),
+            vec![
+                vec![64, 67, 72],
+                vec![65, 69, 72],
+                vec![62, 67, 71],
+                vec![64, 67, 72],
+            ]
+        );
+        assert_eq!(voicings[1].root_note(), Note::F3);
+    }
+
+    #[test]
+    fn test_lead_smoother_than_root_position() {
+        let chords = [
+            Chord::Minor7th(Note::D3),
+            Chord::Dominant7th(Note::G3),
+            Chord::Major7th(Note::C3),
+            Chord::Minor7th(Note::A3),
+        ];
+        let leader = VoiceLeader::new(Note::C3, Note::C5);
+        let voicings: Vec<Vec<u8>> = leader.lead(&chords).iter().map(note_numbers).collect();
+        let root_positions: Vec<Vec<u8>> = chords
+            .iter()
+            .map(|chord| {
+                chord
+                    .notes()
+                    .iter()
+                    .map(|note| note.note_number())
+                    .collect()
+            })
+            .collect();
+
+        assert!(total_motion(&voicings) < total_motion(&root_positions));
+        for (voicing, chord) in voicings.iter().zip(&chords) {
+            assert!(voicing.iter().all(|n| (48..=72).contains(n)));
+            let mut expected: Vec<u8> =
+                chord.notes().iter().map(|n| n.note_number() % 12).collect();
+            let mut actual: Vec<u8> = voicing.iter().map(|n| n % 12).collect();
+            expected.sort();
+            actual.sort();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_lead_avoids_parallels() {
+        // NOTE: Root position C -> D moves every voice in parallel
+        assert_eq!(VoiceLeader::parallels(&[48, 52, 55], &[50, 54, 57]), 1);
+        assert_eq!(VoiceLeader::parallels(&[48, 55, 60], &[50, 57, 62]), 2);
+
+        let leader = VoiceLeader::new(Note::C3, Note::C5);
+        let voicings: Vec<Vec<u8>> = leader
+            .lead(&[Chord::Major(Note::C3), Chord::Major(Note::D3)])
+            .iter()
+            .map(note_numbers)
+            .collect();
+        assert_eq!(VoiceLeader::parallels(&voicings[0], &voicings[1]), 0);
+    }
+
+    #[test]
+    fn test_lead_out_of_range() {
+        let leader = VoiceLeader::new(Note::C4, Note::D4);
+        let voicings = leader.lead(&[Chord::Major(Note::C3)]);
+        assert_eq!(voicings[0].notes(), Chord::Major(Note::C3).notes());
+    }
+
+    #[test]
+    fn test_lead_empty() {
+        let leader = VoiceLeader::new(Note::C4, Note::C5);
+        assert_eq!(leader.lead::<Chord>(&[]), vec![]);
+    }
+}
@@ -0,0 +1,329 @@
+use crate::{
+    instrument::Instrument,
+    theory::{Note, NoteValue},
+};
+
+// NOTE: Elektron-style trig conditions, e.g. Ratio(1, 2) is 1:2 and plays on the first of every
+//       two loops of the track, and Pre and Nei follow the last condition of this and the previous track
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Condition {
+    Ratio(u32, u32),
+    Fill,
+    NotFill,
+    Pre,
+    NotPre,
+    Nei,
+    NotNei,
+    First,
+    NotFirst,
+}
+
+pub struct ConditionContext {
+    pub iteration: usize,
+    pub fill: bool,
+    pub previous: bool,
+    pub neighbor: bool,
+}
+
+impl Condition {
+    pub fn is_met(&self, context: &ConditionContext) -> bool {
+        match self {
+            Condition::Ratio(nth, every) => {
+                *nth > 0
+                    && context.iteration.checked_rem(*every as usize) == Some(*nth as usize - 1)
+            }
+            Condition::Fill => context.fill,
+            Condition::NotFill => !context.fill,
+            Condition::Pre => context.previous,
+            Condition::NotPre => !context.previous,
+            Condition::Nei => context.neighbor,
+            Condition::NotNei => !context.neighbor,
+            Condition::First => context.iteration == 0,
+            Condition::NotFirst => context.iteration != 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    note: Note,
+    velocity: f32,
+    length: Option<NoteValue>,
+    probability: f32,
+    condition: Option<Condition>,
+}
+
+impl Step {
+    pub fn new(note: Note) -> Self {
+        Self {
+            note,
+            velocity: 1.0,
+            length: None,
+            probability: 1.0,
+            condition: None,
+        }
+    }
+
+    #[inline]
+    pub fn note(&self) -> &Note {
+        &self.note
+    }
+
+    #[inline]
+    pub fn velocity(&self) -> f32 {
+        self.velocity
+    }
+
+    // NOTE: Steps without a length are held for one step of the sequencer
+    #[inline]
+    pub fn length(&self) -> Option<&NoteValue> {
+        self.length.as_ref()
+    }
+
+    #[inline]
+    pub fn probability(&self) -> f32 {
+        self.probability
+    }
+
+    #[inline]
+    pub fn condition(&self) -> Option<&Condition> {
+        self.condition.as_ref()
+    }
+
+    #[inline]
+    pub fn is_conditional(&self) -> bool {
+        self.condition.is_some() || self.probability < 1.0
+    }
+
+    pub fn with_velocity(mut self, velocity: f32) -> Self {
+        self.velocity = velocity.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_length(mut self, length: NoteValue) -> Self {
+        self.length = Some(length);
+        self
+    }
+
+    pub fn with_probability(mut self, probability: f32) -> Self {
+        self.probability = probability.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_condition(mut self, condition: Condition) -> Self {
+        self.condition = Some(condition);
+        self
+    }
+}
+
+impl From<Note> for Step {
+    #[inline]
+    fn from(value: Note) -> Self {
+        Self::new(value)
+    }
+}
+
+// NOTE: Tracks loop over their own steps, so tracks of different lengths play in polymeter
+pub struct Track {
+    name: String,
+    steps: Vec<Option<Step>>,
+    instrument: Box<dyn Instrument>,
+    muted: bool,
+    soloed: bool,
+    last_condition: bool,
+}
+
+impl Track {
+    pub fn new<S: Into<String>>(name: S, length: usize, instrument: Box<dyn Instrument>) -> Self {
+        Self::with_steps(name, vec![None; length.max(1)], instrument)
+    }
+
+    pub fn with_steps<S: Into<String>>(
+        name: S,
+        steps: Vec<Option<Step>>,
+        instrument: Box<dyn Instrument>,
+    ) -> Self {
+        let steps = if steps.is_empty() { vec![None] } else { steps };
+        Self {
+            name: name.into(),
+            steps,
+            instrument,
+            muted: false,
+            soloed: false,
+            last_condition: false,
+        }
+    }
+
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    // NOTE: Never true, as a track keeps at least one step
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    // NOTE: Silent tracks have steps, but no note on any of them
+    #[inline]
+    pub fn is_silent(&self) -> bool {
+        self.steps.iter().all(Option::is_none)
+    }
+
+    #[inline]
+    pub fn steps(&self) -> &[Option<Step>] {
+        &self.steps
+    }
+
+    #[inline]
+    pub fn step(&self, count: usize) -> Option<&Step> {
+        self.steps[count % self.steps.len()].as_ref()
+    }
+
+    #[inline]
+    pub fn set_step(&mut self, index: usize, step: Option<Step>) {
+        let len = self.steps.len();
+        self.steps[index % len] = step;
+    }
+
+    pub fn set_length(&mut self, length: usize) {
+        self.steps.resize(length.max(1), None);
+    }
+
+    #[inline]
+    pub fn instrument(&self) -> &dyn Instrument {
+        self.instrument.as_ref()
+    }
+
+    #[inline]
+    pub fn is_muted(&self) -> bool {
+        self.muted
+    }
+
+    #[inline]
+    pub fn set_muted(&mut self, muted: bool) {
+        self.muted = muted;
+    }
+
+    #[inline]
+    pub fn is_soloed(&self) -> bool {
+        self.soloed
+    }
+
+    #[inline]
+    pub fn set_soloed(&mut self, soloed: bool) {
+        self.soloed = soloed;
+    }
+
+    // NOTE: The result of the last conditional step, used by Pre and Nei
+    #[inline]
+    pub fn last_condition(&self) -> bool {
+        self.last_condition
+    }
+
+    #[inline]
+    pub fn set_last_condition(&mut self, last_condition: bool) {
+        self.last_condition = last_condition;
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+    use crate::result::Result;
+
+    pub type Events = Rc<RefCell<Vec<(f64, Note, f32, f64)>>>;
+
+    pub struct Recorder {
+        events: Events,
+    }
+
+    impl Recorder {
+        pub fn new(events: &Events) -> Box<Self> {
+            Box::new(Self {
+                events: events.clone(),
+            })
+        }
+    }
+
+    impl Instrument for Recorder {
+        fn play(&self, time: f64, note: &Note, velocity: f32, duration: f64) -> Result<()> {
+            self.events
+                .borrow_mut()
+                .push((time, note.clone(), velocity, duration));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_step() {
+        let step = Step::new(Note::C4)
+            .with_velocity(1.5)
+            .with_probability(-0.5);
+        assert_eq!(step.velocity(), 1.0);
+        assert_eq!(step.probability(), 0.0);
+        assert_eq!(step.length(), None);
+    }
+
+    #[test]
+    fn test_condition() {
+        let context = |iteration: usize| ConditionContext {
+            iteration,
+            fill: false,
+            previous: true,
+            neighbor: false,
+        };
+        let iterations = |condition: Condition| {
+            (0..8)
+                .filter(|iteration| condition.is_met(&context(*iteration)))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(iterations(Condition::Ratio(1, 2)), vec![0, 2, 4, 6]);
+        assert_eq!(iterations(Condition::Ratio(3, 4)), vec![2, 6]);
+        assert!(iterations(Condition::Ratio(0, 4)).is_empty());
+        assert!(iterations(Condition::Ratio(1, 0)).is_empty());
+        assert_eq!(iterations(Condition::First), vec![0]);
+        assert_eq!(iterations(Condition::NotFirst), (1..8).collect::<Vec<_>>());
+        assert!(!Condition::Fill.is_met(&context(0)));
+        assert!(Condition::NotFill.is_met(&context(0)));
+        assert!(Condition::Pre.is_met(&context(0)));
+        assert!(!Condition::NotPre.is_met(&context(0)));
+        assert!(!Condition::Nei.is_met(&context(0)));
+        assert!(Condition::NotNei.is_met(&context(0)));
+    }
+
+    #[test]
+    fn test_track() {
+        let events = Events::default();
+        let mut track = Track::with_steps(
+            "bass",
+            vec![Some(Note::C2.into()), None, Some(Note::G2.into())],
+            Recorder::new(&events),
+        );
+        assert_eq!(track.len(), 3);
+        assert_eq!(track.step(3), Some(&Step::new(Note::C2)));
+        assert_eq!(track.step(4), None);
+        assert_eq!(track.step(5), Some(&Step::new(Note::G2)));
+
+        track.set_step(4, Some(Note::E2.into()));
+        assert_eq!(track.step(1), Some(&Step::new(Note::E2)));
+
+        track.set_length(4);
+        assert_eq!(track.step(3), None);
+        assert!(!track.is_silent());
+
+        let track = Track::new("silent", 0, Recorder::new(&events));
+        assert_eq!(track.len(), 1);
+        assert!(!track.is_empty());
+        assert!(track.is_silent());
+    }
+}
@@ -0,0 +1,75 @@
+use wasm_bindgen::prelude::*;
+
+// NOTE: Bars and beats count from zero, beats are units of the time signature, e.g. eighths in 6/8
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transport {
+    bar: usize,
+    beat: usize,
+    page: usize,
+    step: usize,
+    time: f64,
+    song_time: f64,
+    next_time: f64,
+}
+
+#[wasm_bindgen]
+impl Transport {
+    #[wasm_bindgen]
+    pub fn bar(&self) -> usize {
+        self.bar
+    }
+
+    #[wasm_bindgen]
+    pub fn beat(&self) -> usize {
+        self.beat
+    }
+
+    #[wasm_bindgen]
+    pub fn page(&self) -> usize {
+        self.page
+    }
+
+    #[wasm_bindgen]
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    // NOTE: The AudioContext time the step was scheduled at
+    #[wasm_bindgen]
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    #[wasm_bindgen]
+    pub fn song_time(&self) -> f64 {
+        self.song_time
+    }
+
+    #[wasm_bindgen]
+    pub fn next_time(&self) -> f64 {
+        self.next_time
+    }
+}
+
+impl Transport {
+    pub(crate) fn new(bar: usize, beat: usize, page: usize, step: usize, time: f64) -> Self {
+        Self {
+            bar,
+            beat,
+            page,
+            step,
+            time,
+            song_time: 0.0,
+            next_time: time,
+        }
+    }
+
+    pub(crate) fn at(&self, song_time: f64, next_time: f64) -> Self {
+        Self {
+            song_time,
+            next_time,
+            ..self.clone()
+        }
+    }
+}
@@ -29,6 +29,11 @@ impl WebWorker {
         self.closure = Some(closure);
     }
 
+    #[inline]
+    pub fn handle(&self) -> &Worker {
+        &self.handle
+    }
+
     pub fn post_message(&self, s: &str) -> Result<()> {
         self.handle.post_message(&JsValue::from_str(s))?;
         Ok(())